@@ -419,6 +419,12 @@ pub enum WindowEvent<'a> {
     CursorLeft { device_id: DeviceId },
 
     /// A mouse wheel movement or touchpad scroll occurred.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The mouse wheels only send the [`TouchPhase::Moved`] phase. The touchpad and
+    ///   the other continuous sources start with [`TouchPhase::Started`] and send
+    ///   [`TouchPhase::Ended`] once the fingers are lifted, where kinetic scrolling could start.
     MouseWheel {
         device_id: DeviceId,
         delta: MouseScrollDelta,
@@ -439,7 +445,7 @@ pub enum WindowEvent<'a> {
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS**.
+    /// - Only available on **macOS** and **Wayland**.
     TouchpadMagnify {
         device_id: DeviceId,
         delta: f64,
@@ -472,7 +478,7 @@ pub enum WindowEvent<'a> {
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS**.
+    /// - Only available on **macOS** and **Wayland**.
     TouchpadRotate {
         device_id: DeviceId,
         delta: f32,
@@ -529,7 +535,9 @@ pub enum WindowEvent<'a> {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / X11 / Wayland / Orbital:** Unsupported.
+    /// - **Wayland:** Requires the `wayland-color-scheme` feature. Only sent to the windows without
+    ///   the explicit theme, when the XDG settings portal prefers a dark or light color scheme.
+    /// - **iOS / Android / X11 / Orbital:** Unsupported.
     ThemeChanged(Theme),
 
     /// The window has been occluded (completely hidden from view).
@@ -1121,7 +1129,7 @@ pub struct Touch {
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **iOS** 9.0+ and **Windows** 8+.
+    /// - Only available on **iOS** 9.0+, **Windows** 8+, and **Wayland** for tablet tools.
     pub force: Option<Force>,
     /// Unique identifier of a finger.
     pub id: u64,
@@ -3,16 +3,22 @@ use std::os::raw;
 use sctk::reexports::client::Proxy;
 
 use crate::{
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Size},
+    error::ExternalError,
     event_loop::{EventLoopBuilder, EventLoopWindowTarget},
     monitor::MonitorHandle,
-    window::{Window, WindowBuilder},
+    window::{CursorIcon, ImePurpose, Window, WindowBuilder},
 };
 
 use crate::platform_impl::{
     ApplicationName, Backend, EventLoopWindowTarget as LinuxEventLoopWindowTarget,
-    Window as LinuxWindow,
+    MonitorHandle as LinuxMonitorHandle, Window as LinuxWindow,
 };
 
+pub use crate::platform_impl::wayland::{
+    BufferTransform, ContentType, CursorGrabLifetime, DecorationMode, DragOutcome, ForeignToplevel,
+    Subsurface, TabletToolState, TabletToolType, TiledEdges, TitlebarDoubleClickAction,
+};
 pub use crate::window::Theme;
 
 /// Additional methods on [`EventLoopWindowTarget`] that are specific to Wayland.
@@ -29,6 +35,20 @@ pub trait EventLoopWindowTargetExtWayland {
     ///
     /// [`EventLoop`]: crate::event_loop::EventLoop
     fn wayland_display(&self) -> Option<*mut raw::c_void>;
+
+    /// Returns the toplevel windows of all clients, as reported by the compositor through
+    /// `ext-foreign-toplevel-list-v1`.
+    ///
+    /// The list is updated while the event loop dispatches Wayland events, so it should be
+    /// queried again after each [`Event::MainEventsCleared`] to keep track of the appearing and
+    /// closing toplevels.
+    ///
+    /// Returns an empty list if the compositor doesn't support the protocol, or if the
+    /// [`EventLoop`] doesn't use wayland.
+    ///
+    /// [`Event::MainEventsCleared`]: crate::event::Event::MainEventsCleared
+    /// [`EventLoop`]: crate::event_loop::EventLoop
+    fn foreign_toplevels(&self) -> Vec<ForeignToplevel>;
 }
 
 impl<T> EventLoopWindowTargetExtWayland for EventLoopWindowTarget<T> {
@@ -47,6 +67,15 @@ impl<T> EventLoopWindowTargetExtWayland for EventLoopWindowTarget<T> {
             _ => None,
         }
     }
+
+    #[inline]
+    fn foreign_toplevels(&self) -> Vec<ForeignToplevel> {
+        match self.p {
+            LinuxEventLoopWindowTarget::Wayland(ref p) => p.foreign_toplevels(),
+            #[cfg(x11_platform)]
+            _ => Vec::new(),
+        }
+    }
 }
 
 /// Additional methods on [`EventLoopBuilder`] that are specific to Wayland.
@@ -59,6 +88,14 @@ pub trait EventLoopBuilderExtWayland {
     /// By default, the window is only allowed to be created on the main
     /// thread, to make platform compatibility easier.
     fn with_any_thread(&mut self, any_thread: bool) -> &mut Self;
+
+    /// Use the cursor theme with the given name and base size instead of the one from the
+    /// `XCURSOR_THEME` and `XCURSOR_SIZE` environment variables.
+    ///
+    /// The `size` assumes the scale factor of 1, the cursors are scaled with the scale factor of
+    /// the surface they are drawn on. When the theme is set, the cursors are never drawn by the
+    /// compositor.
+    fn with_cursor_theme(&mut self, name: impl Into<String>, size: u32) -> &mut Self;
 }
 
 impl<T> EventLoopBuilderExtWayland for EventLoopBuilder<T> {
@@ -73,6 +110,12 @@ impl<T> EventLoopBuilderExtWayland for EventLoopBuilder<T> {
         self.platform_specific.any_thread = any_thread;
         self
     }
+
+    #[inline]
+    fn with_cursor_theme(&mut self, name: impl Into<String>, size: u32) -> &mut Self {
+        self.platform_specific.cursor_theme = Some((name.into(), size));
+        self
+    }
 }
 
 /// Additional methods on [`Window`] that are specific to Wayland.
@@ -90,8 +133,298 @@ pub trait WindowExtWayland {
     ///
     /// The pointer will become invalid when the [`Window`] is destroyed.
     fn wayland_display(&self) -> Option<*mut raw::c_void>;
+
+    /// Inhibit the idle behavior of the compositor, like screen blanking or locking, while the
+    /// window is visible.
+    ///
+    /// Does nothing when the compositor doesn't support `zwp_idle_inhibit_manager_v1` or the
+    /// window doesn't use wayland.
+    fn set_idle_inhibit(&self, inhibit: bool);
+
+    /// Hint the compositor about the kind of content displayed by the window, so it could, for
+    /// example, allow tearing for games.
+    ///
+    /// The hint is applied with the next commit of the window surface.
+    ///
+    /// Does nothing when the compositor doesn't support `wp_content_type_manager_v1` or the
+    /// window doesn't use wayland.
+    fn set_content_type(&self, content_type: ContentType);
+
+    /// Fill the window with the given non-premultiplied RGBA color, without allocating a buffer of
+    /// the window size.
+    ///
+    /// The color is attached and committed right away, replacing the buffer of the renderer
+    /// which owns the window surface, so it should not be combined with other rendering to it.
+    ///
+    /// The color can't be set while a source rectangle is set with
+    /// [`WindowExtWayland::set_viewport_source`], since it would be outside of the single pixel;
+    /// reset it with `None` first. The source rectangle is never discarded by this call.
+    ///
+    /// Returns an error when the viewport source is set, the compositor doesn't support
+    /// `wp_single_pixel_buffer_manager_v1` and `wp_viewporter` with fractional scaling, or the
+    /// window doesn't use wayland.
+    fn set_background_color(&self, color: [f32; 4]) -> Result<(), ExternalError>;
+
+    /// Crop the buffers the client attaches to the window surface to the rectangle of the given
+    /// origin and size, which is then scaled to fill the window, e.g. to letterbox a video
+    /// without another surface. `None` shows the whole buffer again.
+    ///
+    /// The rectangle is in the buffer coordinates, which are physical pixels, and it must be
+    /// within the buffer. It's kept across resizes, and
+    /// [`WindowExtWayland::set_background_color`] fails while it's set.
+    ///
+    /// Returns an error when the rectangle is not finite, has a negative origin, or is smaller
+    /// than 1/256 of a pixel, the compositor doesn't support `wp_viewporter` with fractional
+    /// scaling, or the window doesn't use wayland.
+    fn set_viewport_source(
+        &self,
+        source: Option<(PhysicalPosition<f64>, PhysicalSize<f64>)>,
+    ) -> Result<(), ExternalError>;
+
+    /// Paint the titlebar of the client side decorations with the given non-premultiplied RGBA
+    /// accent color. Passing `None` restores the colors of the current theme.
+    ///
+    /// The accent color is only applied to the titlebar of the focused window.
+    ///
+    /// Does nothing when the window doesn't use wayland, the compositor draws the decorations, or
+    /// the `sctk-adwaita` feature is disabled.
+    fn set_accent_color(&self, accent_color: Option<[f32; 4]>);
+
+    /// Returns whether the decorations of the window are drawn by the client or the compositor.
+    ///
+    /// Returns `None` when the window was not configured yet or doesn't use wayland.
+    fn decoration_mode(&self) -> Option<DecorationMode>;
+
+    /// Returns the edges of the window tiled against other windows or the screen, e.g. to not
+    /// round the corners between them.
+    ///
+    /// Returns an empty set when the window was not configured yet or doesn't use wayland.
+    fn tiled_edges(&self) -> TiledEdges;
+
+    /// Returns the tilt, the pressed buttons and the type of the tablet tool reported as the
+    /// [`Touch`](crate::event::Touch) with the given `id`, while it's in proximity of the window.
+    ///
+    /// The state is updated with each tool event. A change of the tilt or buttons while the tool
+    /// touches the tablet is reported as a [`TouchPhase::Moved`](crate::event::TouchPhase::Moved)
+    /// touch, otherwise it's only visible here.
+    ///
+    /// Returns `None` when no such tool is in proximity of the window, the compositor doesn't
+    /// support `zwp_tablet_manager_v2`, or the window doesn't use wayland.
+    fn tablet_tool_state(&self, id: u64) -> Option<TabletToolState>;
+
+    /// Returns all the monitors the window is currently shown on, in the order the window
+    /// entered them.
+    ///
+    /// Returns an empty list when the window doesn't use wayland.
+    fn current_monitors(&self) -> Vec<MonitorHandle>;
+
+    /// Returns the transform the window content should be rendered with, so the compositor
+    /// doesn't have to rotate it for the outputs the window is shown on, e.g. a monitor mounted
+    /// rotated by 90 degrees.
+    ///
+    /// The transform follows the outputs the window is on, and is [`BufferTransform::Normal`]
+    /// when they have different transforms, the window isn't shown yet, or the window doesn't
+    /// use wayland.
+    fn preferred_buffer_transform(&self) -> BufferTransform;
+
+    /// Set the transform the window content is rendered with, e.g. the
+    /// [`WindowExtWayland::preferred_buffer_transform`], so the compositor undoes it instead of
+    /// rotating the content for the output.
+    ///
+    /// The inner size of the window doesn't change, but the buffers must have their width and
+    /// height swapped when [`BufferTransform::swaps_dimensions`]. The transform applies with the
+    /// next buffer attached to the window, so a redraw is requested.
+    ///
+    /// Does nothing when the window doesn't use wayland.
+    fn set_buffer_transform(&self, transform: BufferTransform);
+
+    /// Returns the transform set with [`WindowExtWayland::set_buffer_transform`].
+    ///
+    /// Returns [`BufferTransform::Normal`] when the window doesn't use wayland.
+    fn buffer_transform(&self) -> BufferTransform;
+
+    /// Returns whether the compositor draws the window as activated, which could differ from
+    /// the keyboard focus, e.g. while a popup of the window has the focus.
+    ///
+    /// Returns `false` when the window was not configured yet or doesn't use wayland.
+    fn is_activated(&self) -> bool;
+
+    /// Request the new inner size of the window, like [`Window::set_inner_size`].
+    ///
+    /// The size is only applied right away while the window is floating, in which case the new
+    /// physical size is returned. Otherwise the compositor dictates the size and the request is
+    /// ignored, so `None` is returned and the size only changes with a later
+    /// [`WindowEvent::Resized`].
+    ///
+    /// Returns `None` when the window doesn't use wayland.
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>>;
+
+    /// Set the action performed on the double click on the titlebar of the client side
+    /// decorations. By default the window is maximized or unmaximized.
+    ///
+    /// The double click is only reported by the decorations while the window could be maximized,
+    /// so the action is never performed when the window is not resizable, has a fixed size, has
+    /// the maximize button disabled, or the compositor doesn't support maximizing. The fallback
+    /// decorations, used when none of the `wayland-csd-adwaita` features is enabled, don't report
+    /// the double click at all.
+    ///
+    /// Does nothing when the window doesn't use wayland.
+    fn set_titlebar_double_click_action(&self, action: TitlebarDoubleClickAction);
+
+    /// Returns the physical thickness of the client side decorations around the content of the
+    /// window as the top, right, bottom, and left insets.
+    ///
+    /// All the insets are zero when the compositor draws the decorations or the window doesn't
+    /// use wayland.
+    fn frame_insets(&self) -> (u32, u32, u32, u32);
+
+    /// Set the parent of the window, so the compositor could stack it above the parent, e.g.
+    /// for dialogs. Passing `None` clears the parent.
+    ///
+    /// Does nothing when the window or the parent don't use wayland.
+    fn set_parent(&self, parent: Option<&Window>);
+
+    /// Request an `xdg-activation` token for the window, which could be passed to another client,
+    /// e.g. through the `XDG_ACTIVATION_TOKEN` environment variable, to let it take the focus.
+    ///
+    /// The `callback` is called with the token from the event loop once the compositor issued
+    /// it.
+    ///
+    /// Returns an error when the compositor doesn't support `xdg_activation_v1` or the window
+    /// doesn't use wayland.
+    fn request_activation_token(
+        &self,
+        callback: impl FnOnce(String) + Send + 'static,
+    ) -> Result<(), ExternalError>;
+
+    /// Activate the window with the `xdg-activation` token received from another client, which
+    /// usually raises and focuses the window.
+    ///
+    /// Does nothing when the compositor doesn't support `xdg_activation_v1` or the window
+    /// doesn't use wayland.
+    fn activate(&self, token: String);
+
+    /// Start a drag from the window offering `mime_data`, one entry per mime type, to the
+    /// clients the data is dropped on.
+    ///
+    /// The drag should be started while a mouse button is held down over the window. The
+    /// `callback` is called from the event loop once the drag finished or was cancelled.
+    ///
+    /// Returns an error when the compositor doesn't support `wl_data_device_manager`, no pointer
+    /// is available, or the window doesn't use wayland.
+    fn start_drag(
+        &self,
+        mime_data: Vec<(String, Vec<u8>)>,
+        callback: impl FnOnce(DragOutcome) + Send + 'static,
+    ) -> Result<(), ExternalError>;
+
+    /// Set the clipboard to `mime_data`, one entry per mime type, e.g.
+    /// `text/plain;charset=utf-8` for the text.
+    ///
+    /// The clipboard could be set only while the window has the keyboard focus.
+    ///
+    /// Returns an error when the compositor doesn't support `wl_data_device_manager`, the window
+    /// doesn't have the keyboard focus, or the window doesn't use wayland.
+    fn set_clipboard(&self, mime_data: Vec<(String, Vec<u8>)>) -> Result<(), ExternalError>;
+
+    /// Set the clipboard to the given text.
+    ///
+    /// See [`set_clipboard`](WindowExtWayland::set_clipboard) for details.
+    fn set_clipboard_text(&self, text: String) -> Result<(), ExternalError>;
+
+    /// Read the clipboard in the given mime type.
+    ///
+    /// The `callback` is called from a separate thread with the data once it was read.
+    ///
+    /// Returns an error when the compositor doesn't support `wl_data_device_manager`, the window
+    /// doesn't have the keyboard focus, the clipboard doesn't offer the mime type, or the window
+    /// doesn't use wayland.
+    fn read_clipboard(
+        &self,
+        mime_type: String,
+        callback: impl FnOnce(Vec<u8>) + Send + 'static,
+    ) -> Result<(), ExternalError>;
+
+    /// Read the text from the clipboard.
+    ///
+    /// See [`read_clipboard`](WindowExtWayland::read_clipboard) for details.
+    fn read_clipboard_text(
+        &self,
+        callback: impl FnOnce(String) + Send + 'static,
+    ) -> Result<(), ExternalError>;
+
+    /// Set the text around the cursor, so the IME could use it for better predictions.
+    ///
+    /// The `cursor` and the `anchor` of the selection are byte offsets into the `text`, equal when
+    /// nothing is selected. The text is trimmed around the cursor when it's longer than 4000
+    /// bytes.
+    ///
+    /// Does nothing when the window doesn't use wayland.
+    fn set_ime_surrounding_text(&self, text: String, cursor: usize, anchor: usize);
+
+    /// Set the region of the window accepting the pointer and touch input, as `(x, y, width,
+    /// height)` rectangles in logical pixels relative to the content. The input outside of the
+    /// region passes through to whatever is below the window.
+    ///
+    /// Passing `None` resets the region to the whole window.
+    ///
+    /// Does nothing when the window doesn't use wayland.
+    fn set_input_region(&self, region: Option<Vec<(i32, i32, u32, u32)>>);
+
+    /// Set the region the cursor is confined to with [`CursorGrabMode::Confined`], as `(x, y,
+    /// width, height)` rectangles in logical pixels relative to the content. The region applies
+    /// right away when the cursor is already confined.
+    ///
+    /// Passing `None` confines the cursor to the whole window, which is the default.
+    ///
+    /// Returns an error when the region couldn't be applied or the window doesn't use wayland.
+    ///
+    /// [`CursorGrabMode::Confined`]: crate::window::CursorGrabMode::Confined
+    fn set_cursor_confine_region(
+        &self,
+        region: Option<Vec<(i32, i32, u32, u32)>>,
+    ) -> Result<(), ExternalError>;
+
+    /// Set whether the cursor grabs set afterwards with [`Window::set_cursor_grab`] persist when
+    /// the compositor releases them, e.g. while the window is unfocused, or end right away.
+    ///
+    /// The grabs are [`CursorGrabLifetime::Persistent`] by default.
+    ///
+    /// Does nothing when the window doesn't use wayland.
+    fn set_cursor_grab_lifetime(&self, lifetime: CursorGrabLifetime);
+
+    /// Set whether the cursor grab set with [`Window::set_cursor_grab`] is set again each time
+    /// the window gains the keyboard focus, for compositors that drop it on the focus loss
+    /// instead of suspending it.
+    ///
+    /// This is disabled by default, and does nothing for the one-shot grabs which already ended.
+    ///
+    /// Does nothing when the window doesn't use wayland.
+    fn set_cursor_regrab_on_focus(&self, regrab_on_focus: bool);
+
+    /// Create a surface composited with the window, which is placed above the window at its top
+    /// left corner by default. The client attaches the buffers to it directly.
+    ///
+    /// The subsurface doesn't take input, which is reported to the window below it instead.
+    ///
+    /// Returns `None` when the window doesn't use wayland.
+    fn create_subsurface(&self) -> Option<Subsurface>;
+
+    /// Set the size the window is never made smaller than, which defaults to 2x1 logical pixels.
+    /// Sizes of zero are raised to one, since the surfaces can't be empty.
+    ///
+    /// The minimum size hint of the compositor is raised to it, keeping the size set with
+    /// [`Window::set_min_inner_size`] when it's larger.
+    ///
+    /// Does nothing when the window doesn't use wayland.
+    fn set_min_window_size(&self, size: LogicalSize<u32>);
 }
 
+/// The mime type of the text in the clipboard.
+const TEXT_MIME_TYPE: &str = "text/plain;charset=utf-8";
+
 impl WindowExtWayland for Window {
     #[inline]
     fn wayland_surface(&self) -> Option<*mut raw::c_void> {
@@ -110,6 +443,326 @@ impl WindowExtWayland for Window {
             _ => None,
         }
     }
+
+    #[inline]
+    fn set_idle_inhibit(&self, inhibit: bool) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_idle_inhibit(inhibit),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn set_content_type(&self, content_type: ContentType) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_content_type(content_type),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn set_accent_color(&self, accent_color: Option<[f32; 4]>) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_accent_color(accent_color),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn decoration_mode(&self) -> Option<DecorationMode> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.decoration_mode(),
+            #[cfg(x11_platform)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.request_inner_size(size),
+            #[cfg(x11_platform)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn is_activated(&self) -> bool {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.is_activated(),
+            #[cfg(x11_platform)]
+            _ => false,
+        }
+    }
+
+    #[inline]
+    fn preferred_buffer_transform(&self) -> BufferTransform {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.preferred_buffer_transform(),
+            #[cfg(x11_platform)]
+            _ => BufferTransform::Normal,
+        }
+    }
+
+    #[inline]
+    fn set_buffer_transform(&self, transform: BufferTransform) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_buffer_transform(transform),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn buffer_transform(&self) -> BufferTransform {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.buffer_transform(),
+            #[cfg(x11_platform)]
+            _ => BufferTransform::Normal,
+        }
+    }
+
+    #[inline]
+    fn current_monitors(&self) -> Vec<MonitorHandle> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w
+                .current_monitors()
+                .into_iter()
+                .map(|monitor| MonitorHandle {
+                    inner: LinuxMonitorHandle::Wayland(monitor),
+                })
+                .collect(),
+            #[cfg(x11_platform)]
+            _ => Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn tiled_edges(&self) -> TiledEdges {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.tiled_edges(),
+            #[cfg(x11_platform)]
+            _ => TiledEdges::empty(),
+        }
+    }
+
+    #[inline]
+    fn tablet_tool_state(&self, id: u64) -> Option<TabletToolState> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.tablet_tool_state(id),
+            #[cfg(x11_platform)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn set_titlebar_double_click_action(&self, action: TitlebarDoubleClickAction) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_titlebar_double_click_action(action),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn frame_insets(&self) -> (u32, u32, u32, u32) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.frame_insets(),
+            #[cfg(x11_platform)]
+            _ => (0, 0, 0, 0),
+        }
+    }
+
+    #[inline]
+    fn set_parent(&self, parent: Option<&Window>) {
+        let parent = match parent.map(|parent| &parent.window) {
+            Some(LinuxWindow::Wayland(parent)) => Some(parent),
+            #[cfg(x11_platform)]
+            Some(_) => return,
+            None => None,
+        };
+
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_parent(parent),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn request_activation_token(
+        &self,
+        callback: impl FnOnce(String) + Send + 'static,
+    ) -> Result<(), ExternalError> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.request_activation_token(Box::new(callback)),
+            #[cfg(x11_platform)]
+            _ => Err(ExternalError::NotSupported(
+                crate::error::NotSupportedError::new(),
+            )),
+        }
+    }
+
+    #[inline]
+    fn activate(&self, token: String) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.activate(token),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn start_drag(
+        &self,
+        mime_data: Vec<(String, Vec<u8>)>,
+        callback: impl FnOnce(DragOutcome) + Send + 'static,
+    ) -> Result<(), ExternalError> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.start_drag(mime_data, Box::new(callback)),
+            #[cfg(x11_platform)]
+            _ => Err(ExternalError::NotSupported(
+                crate::error::NotSupportedError::new(),
+            )),
+        }
+    }
+
+    #[inline]
+    fn set_clipboard(&self, mime_data: Vec<(String, Vec<u8>)>) -> Result<(), ExternalError> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_clipboard(mime_data),
+            #[cfg(x11_platform)]
+            _ => Err(ExternalError::NotSupported(
+                crate::error::NotSupportedError::new(),
+            )),
+        }
+    }
+
+    #[inline]
+    fn set_clipboard_text(&self, text: String) -> Result<(), ExternalError> {
+        self.set_clipboard(vec![(TEXT_MIME_TYPE.to_owned(), text.into_bytes())])
+    }
+
+    #[inline]
+    fn read_clipboard(
+        &self,
+        mime_type: String,
+        callback: impl FnOnce(Vec<u8>) + Send + 'static,
+    ) -> Result<(), ExternalError> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.read_clipboard(mime_type, Box::new(callback)),
+            #[cfg(x11_platform)]
+            _ => Err(ExternalError::NotSupported(
+                crate::error::NotSupportedError::new(),
+            )),
+        }
+    }
+
+    #[inline]
+    fn read_clipboard_text(
+        &self,
+        callback: impl FnOnce(String) + Send + 'static,
+    ) -> Result<(), ExternalError> {
+        self.read_clipboard(TEXT_MIME_TYPE.to_owned(), move |data| {
+            callback(String::from_utf8_lossy(&data).into_owned())
+        })
+    }
+
+    #[inline]
+    fn set_ime_surrounding_text(&self, text: String, cursor: usize, anchor: usize) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_ime_surrounding_text(text, cursor, anchor),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn set_input_region(&self, region: Option<Vec<(i32, i32, u32, u32)>>) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_input_region(region),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn set_cursor_grab_lifetime(&self, lifetime: CursorGrabLifetime) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_cursor_grab_lifetime(lifetime),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn set_cursor_regrab_on_focus(&self, regrab_on_focus: bool) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_cursor_regrab_on_focus(regrab_on_focus),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn set_cursor_confine_region(
+        &self,
+        region: Option<Vec<(i32, i32, u32, u32)>>,
+    ) -> Result<(), ExternalError> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_cursor_confine_region(region),
+            #[cfg(x11_platform)]
+            _ => Err(ExternalError::NotSupported(
+                crate::error::NotSupportedError::new(),
+            )),
+        }
+    }
+
+    #[inline]
+    fn create_subsurface(&self) -> Option<Subsurface> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => Some(w.create_subsurface()),
+            #[cfg(x11_platform)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn set_min_window_size(&self, size: LogicalSize<u32>) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_min_window_size(size),
+            #[cfg(x11_platform)]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    fn set_background_color(&self, color: [f32; 4]) -> Result<(), ExternalError> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_background_color(color),
+            #[cfg(x11_platform)]
+            _ => Err(ExternalError::NotSupported(
+                crate::error::NotSupportedError::new(),
+            )),
+        }
+    }
+
+    #[inline]
+    fn set_viewport_source(
+        &self,
+        source: Option<(PhysicalPosition<f64>, PhysicalSize<f64>)>,
+    ) -> Result<(), ExternalError> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_viewport_source(source),
+            #[cfg(x11_platform)]
+            _ => Err(ExternalError::NotSupported(
+                crate::error::NotSupportedError::new(),
+            )),
+        }
+    }
 }
 
 /// Additional methods on [`WindowBuilder`] that are specific to Wayland.
@@ -122,6 +775,31 @@ pub trait WindowBuilderExtWayland {
     /// For details about application ID conventions, see the
     /// [Desktop Entry Spec](https://specifications.freedesktop.org/desktop-entry-spec/desktop-entry-spec-latest.html#desktop-file-id)
     fn with_name(self, general: impl Into<String>, instance: impl Into<String>) -> Self;
+
+    /// Build window with the given content type hint.
+    ///
+    /// See [`WindowExtWayland::set_content_type`] for details.
+    fn with_content_type(self, content_type: ContentType) -> Self;
+
+    /// Build window with the given accent color of the client side decorations titlebar.
+    ///
+    /// See [`WindowExtWayland::set_accent_color`] for details.
+    fn with_accent_color(self, accent_color: [f32; 4]) -> Self;
+
+    /// Build window with the size it's never made smaller than.
+    ///
+    /// See [`WindowExtWayland::set_min_window_size`] for details.
+    fn with_min_window_size(self, size: LogicalSize<u32>) -> Self;
+
+    /// Build window with the given purpose of the IME input.
+    ///
+    /// See [`Window::set_ime_purpose`] for details.
+    fn with_ime_purpose(self, purpose: ImePurpose) -> Self;
+
+    /// Build window with the given cursor icon.
+    ///
+    /// See [`Window::set_cursor_icon`] for details.
+    fn with_cursor_icon(self, cursor: CursorIcon) -> Self;
 }
 
 impl WindowBuilderExtWayland for WindowBuilder {
@@ -130,12 +808,57 @@ impl WindowBuilderExtWayland for WindowBuilder {
         self.platform_specific.name = Some(ApplicationName::new(general.into(), instance.into()));
         self
     }
+
+    #[inline]
+    fn with_content_type(mut self, content_type: ContentType) -> Self {
+        self.platform_specific.content_type = content_type;
+        self
+    }
+
+    #[inline]
+    fn with_accent_color(mut self, accent_color: [f32; 4]) -> Self {
+        self.platform_specific.accent_color = Some(accent_color);
+        self
+    }
+
+    #[inline]
+    fn with_min_window_size(mut self, size: LogicalSize<u32>) -> Self {
+        self.platform_specific.min_window_size = Some(size);
+        self
+    }
+
+    #[inline]
+    fn with_ime_purpose(mut self, purpose: ImePurpose) -> Self {
+        self.platform_specific.ime_purpose = purpose;
+        self
+    }
+
+    #[inline]
+    fn with_cursor_icon(mut self, cursor: CursorIcon) -> Self {
+        self.platform_specific.cursor_icon = cursor;
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to Wayland.
 pub trait MonitorHandleExtWayland {
     /// Returns the inner identifier of the monitor.
     fn native_id(&self) -> u32;
+
+    /// Returns the human-readable description of the monitor, e.g. `Foocorp 11" Display`.
+    ///
+    /// Returns `None` if the compositor doesn't provide a description.
+    fn description(&self) -> Option<String>;
+
+    /// Returns the position of the monitor in the compositor's logical space.
+    ///
+    /// Returns `None` if the compositor doesn't support `xdg-output`.
+    fn logical_position(&self) -> Option<LogicalPosition<i32>>;
+
+    /// Returns the size of the monitor in the compositor's logical space.
+    ///
+    /// Returns `None` if the compositor doesn't support `xdg-output`.
+    fn logical_size(&self) -> Option<LogicalSize<u32>>;
 }
 
 impl MonitorHandleExtWayland for MonitorHandle {
@@ -143,4 +866,31 @@ impl MonitorHandleExtWayland for MonitorHandle {
     fn native_id(&self) -> u32 {
         self.inner.native_identifier()
     }
+
+    #[inline]
+    fn description(&self) -> Option<String> {
+        match self.inner {
+            LinuxMonitorHandle::Wayland(ref m) => m.description(),
+            #[cfg(x11_platform)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn logical_position(&self) -> Option<LogicalPosition<i32>> {
+        match self.inner {
+            LinuxMonitorHandle::Wayland(ref m) => m.logical_position(),
+            #[cfg(x11_platform)]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn logical_size(&self) -> Option<LogicalSize<u32>> {
+        match self.inner {
+            LinuxMonitorHandle::Wayland(ref m) => m.logical_size(),
+            #[cfg(x11_platform)]
+            _ => None,
+        }
+    }
 }
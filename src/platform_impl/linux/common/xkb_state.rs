@@ -347,6 +347,16 @@ impl KbdState {
         self.post_init(state, keymap);
     }
 
+    /// Drop the pending compose sequence, e.g. when it was started with another keymap.
+    #[cfg(feature = "wayland")]
+    pub fn reset_compose(&mut self) {
+        for compose_state in [self.xkb_compose_state, self.xkb_compose_state_2] {
+            if !compose_state.is_null() {
+                unsafe { (XKBCH.xkb_compose_state_reset)(compose_state) };
+            }
+        }
+    }
+
     #[cfg(feature = "wayland")]
     pub fn key_repeats(&mut self, keycode: ffi::xkb_keycode_t) -> bool {
         unsafe { (XKBH.xkb_keymap_key_repeats)(self.xkb_keymap, keycode) == 1 }
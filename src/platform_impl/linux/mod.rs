@@ -66,10 +66,12 @@ pub(crate) enum Backend {
     Wayland,
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) forced_backend: Option<Backend>,
     pub(crate) any_thread: bool,
+    #[cfg(wayland_platform)]
+    pub(crate) cursor_theme: Option<(String, u32)>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -97,6 +99,16 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub override_redirect: bool,
     #[cfg(x11_platform)]
     pub x11_window_types: Vec<XWindowType>,
+    #[cfg(wayland_platform)]
+    pub content_type: wayland::ContentType,
+    #[cfg(wayland_platform)]
+    pub accent_color: Option<[f32; 4]>,
+    #[cfg(wayland_platform)]
+    pub min_window_size: Option<crate::dpi::LogicalSize<u32>>,
+    #[cfg(wayland_platform)]
+    pub ime_purpose: ImePurpose,
+    #[cfg(wayland_platform)]
+    pub cursor_icon: CursorIcon,
 }
 
 impl Default for PlatformSpecificWindowBuilderAttributes {
@@ -113,6 +125,16 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
             override_redirect: false,
             #[cfg(x11_platform)]
             x11_window_types: vec![XWindowType::Normal],
+            #[cfg(wayland_platform)]
+            content_type: Default::default(),
+            #[cfg(wayland_platform)]
+            accent_color: None,
+            #[cfg(wayland_platform)]
+            min_window_size: None,
+            #[cfg(wayland_platform)]
+            ime_purpose: ImePurpose::Normal,
+            #[cfg(wayland_platform)]
+            cursor_icon: CursorIcon::Default,
         }
     }
 }
@@ -749,7 +771,8 @@ impl<T: 'static> EventLoop<T> {
         #[cfg(wayland_platform)]
         if attributes.forced_backend == Some(Backend::Wayland) {
             // TODO: Propagate
-            return EventLoop::new_wayland_any_thread().expect("failed to open Wayland connection");
+            return EventLoop::new_wayland_any_thread(attributes)
+                .expect("failed to open Wayland connection");
         }
 
         if let Ok(env_var) = env::var(BACKEND_PREFERENCE_ENV_VAR) {
@@ -764,7 +787,7 @@ impl<T: 'static> EventLoop<T> {
                 }
                 "wayland" => {
                     #[cfg(wayland_platform)]
-                    return EventLoop::new_wayland_any_thread()
+                    return EventLoop::new_wayland_any_thread(attributes)
                         .expect("Failed to initialize Wayland backend");
                     #[cfg(not(wayland_platform))]
                     panic!("wayland feature is not enabled");
@@ -776,7 +799,7 @@ impl<T: 'static> EventLoop<T> {
         }
 
         #[cfg(wayland_platform)]
-        let wayland_err = match EventLoop::new_wayland_any_thread() {
+        let wayland_err = match EventLoop::new_wayland_any_thread(attributes) {
             Ok(event_loop) => return event_loop,
             Err(err) => err,
         };
@@ -798,8 +821,10 @@ impl<T: 'static> EventLoop<T> {
     }
 
     #[cfg(wayland_platform)]
-    fn new_wayland_any_thread() -> Result<EventLoop<T>, Box<dyn Error>> {
-        wayland::EventLoop::new().map(|evlp| EventLoop::Wayland(Box::new(evlp)))
+    fn new_wayland_any_thread(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<EventLoop<T>, Box<dyn Error>> {
+        wayland::EventLoop::new(attributes).map(|evlp| EventLoop::Wayland(Box::new(evlp)))
     }
 
     #[cfg(x11_platform)]
@@ -0,0 +1,67 @@
+//! Tracking of the preferred color scheme exposed by the XDG settings portal.
+
+use futures_util::StreamExt;
+use sctk::reexports::calloop::channel::Sender;
+use zbus::zvariant::{OwnedValue, Value};
+
+use crate::window::Theme;
+
+const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const SETTINGS_INTERFACE: &str = "org.freedesktop.portal.Settings";
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+const COLOR_SCHEME_KEY: &str = "color-scheme";
+
+/// Follow the preferred color scheme, sending the current one and then each change.
+///
+/// `None` is sent when there's no preference. The future runs until the portal connection is
+/// lost or the receiving side is gone.
+pub async fn follow_color_scheme(sender: Sender<Option<Theme>>) -> zbus::Result<()> {
+    let connection = zbus::Connection::session().await?;
+    let proxy = zbus::Proxy::new(
+        &connection,
+        PORTAL_DESTINATION,
+        PORTAL_PATH,
+        SETTINGS_INTERFACE,
+    )
+    .await?;
+
+    // Subscribe before reading the current value, so no change is missed in between.
+    let mut changes = proxy.receive_signal("SettingChanged").await?;
+
+    let current: OwnedValue = proxy
+        .call("Read", &(APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY))
+        .await?;
+    if sender.send(color_scheme_to_theme(&current)).is_err() {
+        return Ok(());
+    }
+
+    while let Some(message) = changes.next().await {
+        let (namespace, key, value) = match message.body::<(String, String, OwnedValue)>() {
+            Ok(body) => body,
+            Err(_) => continue,
+        };
+
+        if namespace != APPEARANCE_NAMESPACE || key != COLOR_SCHEME_KEY {
+            continue;
+        }
+
+        // The event loop is gone.
+        if sender.send(color_scheme_to_theme(&value)).is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert the `color-scheme` setting, where `0` is no preference, `1` is dark, and `2` is light.
+fn color_scheme_to_theme(value: &Value<'_>) -> Option<Theme> {
+    match value {
+        // `Read` wraps the value into another variant.
+        Value::Value(value) => color_scheme_to_theme(value),
+        Value::U32(1) => Some(Theme::Dark),
+        Value::U32(2) => Some(Theme::Light),
+        _ => None,
+    }
+}
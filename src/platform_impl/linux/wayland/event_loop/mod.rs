@@ -21,6 +21,7 @@ use crate::event::{Event, StartCause, WindowEvent};
 use crate::event_loop::{ControlFlow, EventLoopWindowTarget as RootEventLoopWindowTarget};
 use crate::platform_impl::platform::sticky_exit_callback;
 use crate::platform_impl::EventLoopWindowTarget as PlatformEventLoopWindowTarget;
+use crate::platform_impl::PlatformSpecificEventLoopAttributes;
 
 mod proxy;
 pub mod sink;
@@ -28,6 +29,8 @@ pub mod sink;
 pub use proxy::EventLoopProxy;
 use sink::EventSink;
 
+#[cfg(feature = "wayland-color-scheme")]
+use super::color_scheme;
 use super::state::{WindowCompositorUpdate, WinitState};
 use super::{DeviceId, WindowId};
 
@@ -59,7 +62,9 @@ pub struct EventLoop<T: 'static> {
 }
 
 impl<T: 'static> EventLoop<T> {
-    pub fn new() -> Result<EventLoop<T>, Box<dyn Error>> {
+    pub fn new(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<EventLoop<T>, Box<dyn Error>> {
         let connection = Connection::connect_to_env()?;
 
         let (globals, mut event_queue) = globals::registry_queue_init(&connection)?;
@@ -69,6 +74,9 @@ impl<T: 'static> EventLoop<T> {
 
         let mut winit_state = WinitState::new(&globals, &queue_handle, event_loop.handle())?;
 
+        // Set the cursor theme before the pointers are created during the roundtrip.
+        winit_state.cursor_theme = attributes.cursor_theme.clone();
+
         // NOTE: do a roundtrip after binding the globals to prevent potential
         // races with the server.
         event_queue.roundtrip(&mut winit_state)?;
@@ -96,6 +104,50 @@ impl<T: 'static> EventLoop<T> {
                 }
             })?;
 
+        // Follow the system color scheme on the windows without the explicit theme. The portal
+        // connection is driven by the event loop, so it's closed together with it.
+        #[cfg(feature = "wayland-color-scheme")]
+        {
+            let (executor, scheduler) = calloop::futures::executor()?;
+            let (color_scheme_sender, color_scheme_channel) = calloop::channel::channel();
+            scheduler.schedule(async move {
+                if let Err(err) = color_scheme::follow_color_scheme(color_scheme_sender).await {
+                    info!("Can't follow the preferred color scheme: {err}");
+                }
+            })?;
+            event_loop.handle().insert_source(executor, |(), _, _| ())?;
+
+            event_loop.handle().insert_source(
+                color_scheme_channel,
+                |event, _, winit_state: &mut WinitState| {
+                    let theme = match event {
+                        calloop::channel::Event::Msg(theme) => theme,
+                        calloop::channel::Event::Closed => return,
+                    };
+
+                    if winit_state.system_theme == theme {
+                        return;
+                    }
+                    winit_state.system_theme = theme;
+
+                    for (window_id, window) in winit_state.windows.get_mut() {
+                        let mut window = window.lock().unwrap();
+                        if window.theme().is_some() {
+                            continue;
+                        }
+
+                        // Without a preference the windows are back to the default theme.
+                        window.reload_system_theme(theme);
+                        if let Some(theme) = theme {
+                            winit_state
+                                .events_sink
+                                .push_window_event(WindowEvent::ThemeChanged(theme), *window_id);
+                        }
+                    }
+                },
+            )?;
+        }
+
         // An event's loop awakener to wake up for window events from winit's windows.
         let (event_loop_awakener, event_loop_awakener_source) = calloop::ping::make_ping()?;
         event_loop
@@ -7,9 +7,17 @@ use sctk::reexports::client::Proxy;
 
 pub use crate::platform_impl::platform::WindowId;
 pub use event_loop::{EventLoop, EventLoopProxy, EventLoopWindowTarget};
-pub use output::{MonitorHandle, VideoMode};
-pub use window::Window;
+pub use output::{BufferTransform, MonitorHandle, VideoMode};
+pub use seat::{DragOutcome, TabletToolState, TabletToolType};
+pub use types::ext_foreign_toplevel_list::ForeignToplevel;
+pub use types::wl_subsurface::Subsurface;
+pub use types::wp_content_type::ContentType;
+pub use window::{
+    CursorGrabLifetime, DecorationMode, TiledEdges, TitlebarDoubleClickAction, Window,
+};
 
+#[cfg(feature = "wayland-color-scheme")]
+mod color_scheme;
 mod event_loop;
 mod output;
 mod seat;
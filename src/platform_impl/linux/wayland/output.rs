@@ -1,9 +1,9 @@
-use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::protocol::wl_output::{Transform, WlOutput};
 use sctk::reexports::client::Proxy;
 
 use sctk::output::OutputData;
 
-use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use crate::platform_impl::platform::{
     MonitorHandle as PlatformMonitorHandle, VideoMode as PlatformVideoMode,
 };
@@ -45,6 +45,30 @@ impl MonitorHandle {
         output_data.with_output_info(|info| info.name.clone())
     }
 
+    #[inline]
+    pub fn description(&self) -> Option<String> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| info.description.clone())
+    }
+
+    #[inline]
+    pub fn logical_position(&self) -> Option<LogicalPosition<i32>> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| info.logical_position.map(Into::into))
+    }
+
+    #[inline]
+    pub fn logical_size(&self) -> Option<LogicalSize<u32>> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| {
+            let (width, height) = info.logical_size?;
+            Some(LogicalSize::new(
+                u32::try_from(width).ok()?,
+                u32::try_from(height).ok()?,
+            ))
+        })
+    }
+
     #[inline]
     pub fn native_identifier(&self) -> u32 {
         let output_data = self.proxy.data::<OutputData>().unwrap();
@@ -76,13 +100,21 @@ impl MonitorHandle {
     #[inline]
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
         let output_data = self.proxy.data::<OutputData>().unwrap();
+        // The refresh rate is zero when the compositor doesn't know it.
         output_data.with_output_info(|info| {
             info.modes
                 .iter()
                 .find_map(|mode| mode.current.then_some(mode.refresh_rate as u32))
+                .filter(|&refresh_rate| refresh_rate != 0)
         })
     }
 
+    #[inline]
+    pub fn transform(&self) -> BufferTransform {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| info.transform.into())
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> i32 {
         let output_data = self.proxy.data::<OutputData>().unwrap();
@@ -115,6 +147,66 @@ impl PartialEq for MonitorHandle {
 
 impl Eq for MonitorHandle {}
 
+/// The rotation and flip of a buffer relative to the output it's shown on, e.g. for outputs
+/// mounted rotated by 90 degrees.
+///
+/// The rotations are counter-clockwise, and the flips are around the vertical axis applied
+/// before the rotation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BufferTransform {
+    #[default]
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+}
+
+impl BufferTransform {
+    /// Whether the transform swaps the width and the height of the buffer.
+    #[inline]
+    pub fn swaps_dimensions(self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90 | Self::Rotate270 | Self::Flipped90 | Self::Flipped270
+        )
+    }
+}
+
+impl From<Transform> for BufferTransform {
+    fn from(transform: Transform) -> Self {
+        match transform {
+            Transform::Normal => Self::Normal,
+            Transform::_90 => Self::Rotate90,
+            Transform::_180 => Self::Rotate180,
+            Transform::_270 => Self::Rotate270,
+            Transform::Flipped => Self::Flipped,
+            Transform::Flipped90 => Self::Flipped90,
+            Transform::Flipped180 => Self::Flipped180,
+            Transform::Flipped270 => Self::Flipped270,
+            _ => Self::Normal,
+        }
+    }
+}
+
+impl From<BufferTransform> for Transform {
+    fn from(transform: BufferTransform) -> Self {
+        match transform {
+            BufferTransform::Normal => Self::Normal,
+            BufferTransform::Rotate90 => Self::_90,
+            BufferTransform::Rotate180 => Self::_180,
+            BufferTransform::Rotate270 => Self::_270,
+            BufferTransform::Flipped => Self::Flipped,
+            BufferTransform::Flipped90 => Self::Flipped90,
+            BufferTransform::Flipped180 => Self::Flipped180,
+            BufferTransform::Flipped270 => Self::Flipped270,
+        }
+    }
+}
+
 impl PartialOrd for MonitorHandle {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
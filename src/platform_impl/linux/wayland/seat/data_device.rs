@@ -0,0 +1,494 @@
+//! Drag and drop and clipboard handling through the data device.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{ErrorKind, Read, Write};
+use std::os::unix::ffi::OsStringExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use percent_encoding::percent_decode_str;
+
+use sctk::reexports::calloop::generic::Generic;
+use sctk::reexports::calloop::{Interest, LoopHandle, Mode, PostAction};
+use sctk::reexports::client::protocol::wl_data_device_manager::DndAction;
+use sctk::reexports::client::protocol::wl_data_source::WlDataSource;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+
+use sctk::compositor::SurfaceData;
+use sctk::data_device_manager::data_device::{
+    DataDevice, DataDeviceData, DataDeviceDataExt, DataDeviceHandler,
+};
+use sctk::data_device_manager::data_offer::{DataDeviceOffer, DataOfferHandler, DragOffer};
+use sctk::data_device_manager::data_source::{
+    DataSourceData, DataSourceDataExt, DataSourceHandler,
+};
+use sctk::data_device_manager::{DataDeviceManagerState, ReadPipe, WritePipe};
+
+use crate::event::WindowEvent;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+use super::WinitSeatState;
+
+/// The MIME type used to drag files.
+const URI_LIST_MIME_TYPE: &str = "text/uri-list";
+
+/// The state of the drag and drop session on the seat.
+#[derive(Debug)]
+pub struct DragState {
+    /// The offer of the session.
+    offer: DragOffer,
+
+    /// The window the offer is over.
+    window_id: WindowId,
+
+    /// Whether the offer has files and was accepted.
+    accepted: bool,
+
+    /// The files being dragged, once they were read from the offer.
+    paths: Option<Vec<PathBuf>>,
+
+    /// Whether the files were dropped on the window.
+    dropped: bool,
+
+    /// Whether the drag has left the window.
+    left: bool,
+}
+
+/// The outcome of the drag started by the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DragOutcome {
+    /// The drag was cancelled, or the data was not accepted.
+    Cancelled,
+
+    /// The data was copied to the target.
+    Copied,
+
+    /// The data was moved to the target, so the window should remove it.
+    Moved,
+}
+
+pub type DragCallback = Box<dyn FnOnce(DragOutcome) + Send>;
+
+pub type ClipboardCallback = Box<dyn FnOnce(Vec<u8>) + Send>;
+
+/// The data associated with the data source.
+pub struct WinitDataSourceData {
+    /// The sctk data source data.
+    sctk_data: DataSourceData,
+
+    /// The data for each offered MIME type.
+    mime_data: Vec<(String, Vec<u8>)>,
+
+    /// The callback to report the outcome of the drag.
+    drag_callback: Mutex<Option<DragCallback>>,
+
+    /// The action selected for the drag.
+    drag_action: Mutex<DndAction>,
+}
+
+impl WinitDataSourceData {
+    pub fn new(mime_data: Vec<(String, Vec<u8>)>, drag_callback: Option<DragCallback>) -> Self {
+        Self {
+            sctk_data: Default::default(),
+            mime_data,
+            drag_callback: Mutex::new(drag_callback),
+            drag_action: Mutex::new(DndAction::empty()),
+        }
+    }
+
+    /// The MIME types offered by the source.
+    pub fn mime_types(&self) -> impl Iterator<Item = &str> {
+        self.mime_data
+            .iter()
+            .map(|(mime_type, _)| mime_type.as_str())
+    }
+
+    /// Report the outcome of the drag.
+    fn finish_drag(&self, outcome: DragOutcome) {
+        if let Some(callback) = self.drag_callback.lock().unwrap().take() {
+            callback(outcome);
+        }
+    }
+}
+
+impl DataSourceDataExt for WinitDataSourceData {
+    fn data_source_data(&self) -> &DataSourceData {
+        &self.sctk_data
+    }
+}
+
+impl DragState {
+    /// Send the drop of the files, completing the session.
+    fn drop_files(&self, state: &mut WinitState, paths: &[PathBuf]) {
+        for path in paths {
+            state
+                .events_sink
+                .push_window_event(WindowEvent::DroppedFile(path.clone()), self.window_id);
+        }
+
+        self.offer.finish();
+        self.offer.destroy();
+    }
+}
+
+impl WinitState {
+    /// Get the state of the seat the given data device belongs to.
+    fn data_device_seat(&mut self, data_device: &DataDevice) -> Option<&mut WinitSeatState> {
+        let data = data_device.data()?;
+        self.seats.values_mut().find(|seat_state| {
+            seat_state
+                .data_device
+                .as_ref()
+                .and_then(|data_device| data_device.data::<DataDeviceData>())
+                .map_or(false, |seat_data| std::ptr::eq(seat_data, data))
+        })
+    }
+
+    /// Get the drag state of the seat the given data device belongs to.
+    fn drag_state(
+        &mut self,
+        data_device: &DataDevice,
+    ) -> Option<&mut Option<Arc<Mutex<DragState>>>> {
+        self.data_device_seat(data_device)
+            .map(|seat_state| &mut seat_state.drag_state)
+    }
+}
+
+impl DataDeviceHandler for WinitState {
+    fn enter(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: DataDevice) {
+        let mut offer = match data_device.data().and_then(|data| data.drag_offer()) {
+            Some(offer) => offer,
+            None => return,
+        };
+
+        // The drag could enter the decorations.
+        let window_id = match offer.surface.data::<SurfaceData>() {
+            Some(data) => wayland::make_wid(data.parent_surface().unwrap_or(&offer.surface)),
+            None => {
+                // The offer is not tracked anywhere, so nothing would destroy it later.
+                offer.destroy();
+                return;
+            }
+        };
+
+        let has_files = data_device
+            .data()
+            .map(|data| data.drag_mime_types())
+            .unwrap_or_default()
+            .iter()
+            .any(|mime_type| mime_type == URI_LIST_MIME_TYPE);
+
+        // Only the files are accepted.
+        let pipe = if has_files && self.windows.get_mut().contains_key(&window_id) {
+            offer.receive(URI_LIST_MIME_TYPE.to_owned()).map_err(|err| {
+                warn!("Failed to receive the dragged files: {err}");
+            })
+        } else {
+            Err(())
+        };
+
+        let drag_state = Arc::new(Mutex::new(DragState {
+            offer: offer.clone(),
+            window_id,
+            accepted: pipe.is_ok(),
+            paths: None,
+            dropped: false,
+            left: false,
+        }));
+
+        if let Some(seat_drag_state) = self.drag_state(&data_device) {
+            *seat_drag_state = Some(drag_state.clone());
+        }
+
+        let pipe = match pipe {
+            Ok(pipe) => pipe,
+            Err(()) => {
+                offer.accept_mime_type(offer.serial, None);
+                offer.set_actions(DndAction::empty(), DndAction::empty());
+                return;
+            }
+        };
+
+        offer.accept_mime_type(offer.serial, Some(URI_LIST_MIME_TYPE.to_owned()));
+        offer.set_actions(DndAction::Copy, DndAction::Copy);
+
+        read_pipe(&self.loop_handle, pipe, move |state, data| {
+            let mut drag_state = drag_state.lock().unwrap();
+            if drag_state.left {
+                return;
+            }
+
+            let paths = parse_uri_list(&data);
+            for path in &paths {
+                state.events_sink.push_window_event(
+                    WindowEvent::HoveredFile(path.clone()),
+                    drag_state.window_id,
+                );
+            }
+
+            // The files were dropped before they were read.
+            if drag_state.dropped {
+                drag_state.drop_files(state, &paths);
+            }
+
+            drag_state.paths = Some(paths);
+        });
+    }
+
+    fn leave(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: DataDevice) {
+        let drag_state = match self.drag_state(&data_device).and_then(Option::take) {
+            Some(drag_state) => drag_state,
+            None => return,
+        };
+
+        let mut drag_state = drag_state.lock().unwrap();
+        drag_state.left = true;
+
+        if drag_state.paths.is_some() {
+            self.events_sink
+                .push_window_event(WindowEvent::HoveredFileCancelled, drag_state.window_id);
+        }
+
+        drag_state.offer.destroy();
+    }
+
+    fn motion(&mut self, _: &Connection, _: &QueueHandle<Self>, _: DataDevice) {}
+
+    fn selection(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: DataDevice) {
+        let offer = data_device.data().and_then(|data| data.selection_offer());
+        let seat_state = match self.data_device_seat(&data_device) {
+            Some(seat_state) => seat_state,
+            None => return,
+        };
+
+        // The previous offer is not used by the compositor anymore.
+        if let Some(old_offer) = std::mem::replace(&mut seat_state.selection_offer, offer) {
+            old_offer.destroy();
+        }
+    }
+
+    fn drop_performed(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: DataDevice) {
+        // The leave is sent after the drop, so forget the session right away.
+        let drag_state = match self.drag_state(&data_device).and_then(Option::take) {
+            Some(drag_state) => drag_state,
+            None => return,
+        };
+
+        let mut drag_state = drag_state.lock().unwrap();
+        drag_state.dropped = true;
+
+        if !drag_state.accepted {
+            drag_state.offer.destroy();
+            return;
+        }
+
+        // Otherwise the files are dropped once they're read.
+        if let Some(paths) = drag_state.paths.take() {
+            drag_state.drop_files(self, &paths);
+        }
+    }
+}
+
+impl DataOfferHandler for WinitState {
+    fn offer(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &mut DataDeviceOffer, _: String) {
+    }
+
+    fn source_actions(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+
+    fn selected_action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+}
+
+impl DataSourceHandler for WinitState {
+    fn accept_mime(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: Option<String>,
+    ) {
+    }
+
+    fn send_request(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+        mime: String,
+        fd: WritePipe,
+    ) {
+        let data = source.data::<WinitDataSourceData>().and_then(|data| {
+            data.mime_data
+                .iter()
+                .find(|(mime_type, _)| *mime_type == mime)
+        });
+
+        // The pipe is closed right away when there's nothing to send.
+        if let Some((_, data)) = data {
+            write_pipe(&self.loop_handle, fd, data.clone());
+        }
+    }
+
+    fn cancelled(&mut self, _: &Connection, _: &QueueHandle<Self>, source: &WlDataSource) {
+        if let Some(data) = source.data::<WinitDataSourceData>() {
+            data.finish_drag(DragOutcome::Cancelled);
+        }
+
+        source.destroy();
+    }
+
+    fn dnd_dropped(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource) {}
+
+    fn dnd_finished(&mut self, _: &Connection, _: &QueueHandle<Self>, source: &WlDataSource) {
+        if let Some(data) = source.data::<WinitDataSourceData>() {
+            let outcome = if *data.drag_action.lock().unwrap() == DndAction::Move {
+                DragOutcome::Moved
+            } else {
+                DragOutcome::Copied
+            };
+            data.finish_drag(outcome);
+        }
+
+        source.destroy();
+    }
+
+    fn action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+        action: DndAction,
+    ) {
+        if let Some(data) = source.data::<WinitDataSourceData>() {
+            *data.drag_action.lock().unwrap() = action;
+        }
+    }
+}
+
+/// Write the data to the pipe on the event loop and close it.
+pub fn write_pipe(loop_handle: &LoopHandle<'static, WinitState>, pipe: WritePipe, data: Vec<u8>) {
+    // SAFETY: the file descriptor is owned by the pipe.
+    let file = unsafe { File::from_raw_fd(pipe.into_raw_fd()) };
+
+    // Don't block the event loop when the reader is slow.
+    unsafe {
+        let flags = libc::fcntl(file.as_raw_fd(), libc::F_GETFL);
+        libc::fcntl(file.as_raw_fd(), libc::F_SETFL, flags | libc::O_NONBLOCK);
+    }
+
+    let source = Generic::new(file, Interest::WRITE, Mode::Level);
+    let mut written = 0;
+    let result = loop_handle.insert_source(source, move |_, file, _| {
+        match file.write(&data[written..]) {
+            Ok(len) => written += len,
+            Err(err) if matches!(err.kind(), ErrorKind::Interrupted | ErrorKind::WouldBlock) => (),
+            // The reader has closed the pipe.
+            Err(_) => return Ok(PostAction::Remove),
+        }
+
+        if written < data.len() {
+            Ok(PostAction::Continue)
+        } else {
+            Ok(PostAction::Remove)
+        }
+    });
+
+    if let Err(err) = result {
+        warn!("Failed to write the data source: {err}");
+    }
+}
+
+/// Read the pipe to the end on the event loop and pass the data to the `callback`.
+pub fn read_pipe<F>(loop_handle: &LoopHandle<'static, WinitState>, pipe: ReadPipe, callback: F)
+where
+    F: FnOnce(&mut WinitState, Vec<u8>) + 'static,
+{
+    // SAFETY: the file descriptor is owned by the pipe.
+    let file = unsafe { File::from_raw_fd(pipe.into_raw_fd()) };
+    let source = Generic::new(file, Interest::READ, Mode::Level);
+
+    let mut data = Vec::new();
+    let mut callback = Some(callback);
+    let result = loop_handle.insert_source(source, move |_, file, state| {
+        let mut buf = [0; 4096];
+        match file.read(&mut buf) {
+            Ok(0) => (),
+            Ok(len) => {
+                data.extend_from_slice(&buf[..len]);
+                return Ok(PostAction::Continue);
+            }
+            Err(err) if matches!(err.kind(), ErrorKind::Interrupted | ErrorKind::WouldBlock) => {
+                return Ok(PostAction::Continue);
+            }
+            Err(err) => warn!("Failed to read the data offer: {err}"),
+        }
+
+        if let Some(callback) = callback.take() {
+            callback(state, std::mem::take(&mut data));
+        }
+
+        Ok(PostAction::Remove)
+    });
+
+    if let Err(err) = result {
+        warn!("Failed to read the data offer: {err}");
+    }
+}
+
+/// Read the clipboard pipe to the end on a separate thread and pass the data to the `callback`.
+///
+/// The window could read the clipboard without access to the event loop, and the source could be
+/// the window itself, so the event loop must not be blocked.
+pub fn read_selection(pipe: ReadPipe, callback: ClipboardCallback) {
+    let spawned = thread::Builder::new()
+        .name(String::from("winit clipboard reader"))
+        .spawn(move || {
+            // SAFETY: the file descriptor is owned by the pipe.
+            let mut file = unsafe { File::from_raw_fd(pipe.into_raw_fd()) };
+            let mut data = Vec::new();
+            if let Err(err) = file.read_to_end(&mut data) {
+                warn!("Failed to read the clipboard: {err}");
+            }
+
+            callback(data);
+        });
+
+    if let Err(err) = spawned {
+        warn!("Failed to spawn the clipboard reader: {err}");
+    }
+}
+
+/// Get the paths of the local files from the `text/uri-list`.
+fn parse_uri_list(data: &[u8]) -> Vec<PathBuf> {
+    String::from_utf8_lossy(data)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|uri| uri.strip_prefix("file://"))
+        // Skip the host, like `localhost`.
+        .filter_map(|uri| uri.find('/').map(|start| &uri[start..]))
+        .map(|path| PathBuf::from(OsString::from_vec(percent_decode_str(path).collect())))
+        .collect()
+}
+
+sctk::delegate_data_device_manager!(WinitState);
+sctk::delegate_data_device!(WinitState);
+sctk::delegate_data_offer!(WinitState);
+delegate_dispatch!(WinitState: [WlDataSource: WinitDataSourceData] => DataDeviceManagerState);
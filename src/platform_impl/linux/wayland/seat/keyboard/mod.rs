@@ -43,26 +43,33 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     WlKeymapFormat::NoKeymap => {
                         warn!("non-xkb compatible keymap")
                     }
-                    WlKeymapFormat::XkbV1 => unsafe {
-                        seat_state
-                            .keyboard_state
-                            .as_mut()
-                            .unwrap()
-                            .xkb_state
-                            .init_with_fd(fd, size as usize);
-                    },
+                    WlKeymapFormat::XkbV1 => {
+                        // The keymap is sent again when the user switches the keyboard layout.
+                        let xkb_state = &mut seat_state.keyboard_state.as_mut().unwrap().xkb_state;
+                        unsafe { xkb_state.init_with_fd(fd, size as usize) };
+
+                        // The keysyms of the pending compose sequence were from the old keymap.
+                        xkb_state.reset_compose();
+                    }
                     _ => unreachable!(),
                 },
                 WEnum::Unknown(value) => {
                     warn!("unknown keymap format 0x{:x}", value)
                 }
             },
-            WlKeyboardEvent::Enter { surface, .. } => {
+            WlKeyboardEvent::Enter {
+                surface, serial, ..
+            } => {
                 let window_id = wayland::make_wid(&surface);
 
                 // Mark the window as focused.
                 match state.windows.get_mut().get(&window_id) {
-                    Some(window) => window.lock().unwrap().set_has_focus(true),
+                    Some(window) => {
+                        let mut window = window.lock().unwrap();
+                        window.set_has_focus(true);
+                        window.set_selection_device(seat_state.data_device.clone(), serial);
+                        window.set_focused_text_input(seat_state.text_input.as_deref());
+                    }
                     None => return,
                 };
 
@@ -94,7 +101,11 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 // NOTE: The check whether the window exists is essential as we might get a
                 // nil surface, regardless of what protocol says.
                 match state.windows.get_mut().get(&window_id) {
-                    Some(window) => window.lock().unwrap().set_has_focus(false),
+                    Some(window) => {
+                        let mut window = window.lock().unwrap();
+                        window.set_has_focus(false);
+                        window.unset_selection_device(seat_state.data_device.as_ref());
+                    }
                     None => return,
                 };
 
@@ -115,10 +126,20 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
             WlKeyboardEvent::Key {
                 key,
                 state: key_state,
+                serial,
                 ..
             } if key_state == WEnum::Value(WlKeyState::Pressed) => {
                 let key = key + 8;
 
+                // The clipboard could be set only with the serial of the latest input, and the IME
+                // follows the seat which typed last.
+                let window_id = *data.window_id.lock().unwrap();
+                if let Some(window) = window_id.and_then(|id| state.windows.get_mut().get(&id)) {
+                    let mut window = window.lock().unwrap();
+                    window.set_selection_device(seat_state.data_device.clone(), serial);
+                    window.set_focused_text_input(seat_state.text_input.as_deref());
+                }
+
                 key_input(
                     seat_state,
                     &mut state.events_sink,
@@ -1,31 +1,46 @@
 //! Seat handling.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use fnv::FnvHashMap;
 
+use sctk::reexports::client::protocol::wl_data_device::WlDataDevice;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_touch::WlTouch;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::pointer_gestures::zv1::client::zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1;
 use sctk::reexports::protocols::wp::relative_pointer::zv1::client::zwp_relative_pointer_v1::ZwpRelativePointerV1;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_seat_v2::ZwpTabletSeatV2;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 
+use sctk::data_device_manager::data_device::DataDeviceData;
+use sctk::data_device_manager::data_offer::SelectionOffer;
 use sctk::seat::pointer::{ThemeSpec, ThemedPointer};
 use sctk::seat::{Capability as SeatCapability, SeatHandler, SeatState};
 
 use crate::keyboard::ModifiersState;
 use crate::platform_impl::wayland::state::WinitState;
 
+mod data_device;
 mod keyboard;
 mod pointer;
+mod tablet;
 mod text_input;
 mod touch;
 
+pub use data_device::{
+    read_selection, ClipboardCallback, DragCallback, DragOutcome, WinitDataSourceData,
+};
+pub use pointer::cursor_shape::{cursor_icon_to_shape, CursorShapeManager};
+pub use pointer::pointer_gesture::PointerGesturesState;
 pub use pointer::relative_pointer::RelativePointerState;
 pub use pointer::{PointerConstraintsState, WinitPointerData, WinitPointerDataExt};
-pub use text_input::{TextInputState, ZwpTextInputV3Ext};
+pub use tablet::{TabletManagerState, TabletToolState, TabletToolType};
+pub use text_input::{SurroundingText, TextInputState, ZwpTextInputV3Ext};
 
+use data_device::DragState;
 use keyboard::{KeyboardData, KeyboardState};
+use pointer::pointer_gesture::PinchGestureData;
 use text_input::TextInputData;
 use touch::TouchPoint;
 
@@ -46,6 +61,21 @@ pub struct WinitSeatState {
     /// The relative pointer bound on the seat.
     relative_pointer: Option<ZwpRelativePointerV1>,
 
+    /// The pinch gesture bound on the seat.
+    pinch_gesture: Option<ZwpPointerGesturePinchV1>,
+
+    /// The tablet seat bound on the seat.
+    tablet_seat: Option<ZwpTabletSeatV2>,
+
+    /// The data device bound on the seat.
+    data_device: Option<WlDataDevice>,
+
+    /// The drag and drop session over the window.
+    drag_state: Option<Arc<Mutex<DragState>>>,
+
+    /// The current selection offer, destroyed once it's replaced.
+    selection_offer: Option<SelectionOffer>,
+
     /// The keyboard bound on the seat.
     keyboard_state: Option<KeyboardState>,
 
@@ -62,7 +92,12 @@ impl WinitSeatState {
             pointer: None,
             touch: None,
             relative_pointer: None,
+            pinch_gesture: None,
             text_input: None,
+            tablet_seat: None,
+            data_device: None,
+            drag_state: None,
+            selection_offer: None,
             touch_map: Default::default(),
             keyboard_state: None,
             modifiers: ModifiersState::empty(),
@@ -98,14 +133,13 @@ impl SeatHandler for WinitState {
                 let surface = self.compositor_state.create_surface(queue_handle);
                 let surface_id = surface.id();
                 let pointer_data = WinitPointerData::new(seat.clone(), surface);
+                let theme = match self.cursor_theme.as_ref() {
+                    Some((name, size)) => ThemeSpec::Named { name, size: *size },
+                    None => ThemeSpec::System,
+                };
                 let themed_pointer = self
                     .seat_state
-                    .get_pointer_with_theme_and_data(
-                        queue_handle,
-                        &seat,
-                        ThemeSpec::System,
-                        pointer_data,
-                    )
+                    .get_pointer_with_theme_and_data(queue_handle, &seat, theme, pointer_data)
                     .expect("failed to create pointer with present capability.");
 
                 seat_state.relative_pointer = self.relative_pointer.as_ref().map(|manager| {
@@ -116,6 +150,32 @@ impl SeatHandler for WinitState {
                     )
                 });
 
+                seat_state.pinch_gesture = self.pointer_gestures.as_ref().map(|manager| {
+                    manager.get_pinch_gesture(
+                        themed_pointer.pointer(),
+                        queue_handle,
+                        PinchGestureData::default(),
+                    )
+                });
+
+                // The compositor draws the cursors with its own theme, so the shapes are not used
+                // when the user picked the theme.
+                let cursor_shape_manager = self
+                    .cursor_shape_manager
+                    .as_ref()
+                    .filter(|_| self.cursor_theme.is_none());
+                if let Some(cursor_shape_manager) = cursor_shape_manager {
+                    let cursor_shape_device = cursor_shape_manager.get_pointer(
+                        themed_pointer.pointer(),
+                        queue_handle,
+                        sctk::globals::GlobalData,
+                    );
+                    themed_pointer
+                        .pointer()
+                        .winit_data()
+                        .set_cursor_shape_device(cursor_shape_device);
+                }
+
                 let themed_pointer = Arc::new(themed_pointer);
 
                 // Register cursor surface.
@@ -139,6 +199,40 @@ impl SeatHandler for WinitState {
                 TextInputData::default(),
             )));
         }
+
+        if let Some(tablet_manager) = seat_state
+            .tablet_seat
+            .is_none()
+            .then_some(self.tablet_manager.as_ref())
+            .flatten()
+        {
+            seat_state.tablet_seat = Some(tablet_manager.get_tablet_seat(
+                &seat,
+                queue_handle,
+                sctk::globals::GlobalData,
+            ));
+        }
+
+        if let Some(data_device_manager) = seat_state
+            .data_device
+            .is_none()
+            .then_some(self.data_device_manager.as_ref())
+            .flatten()
+        {
+            seat_state.data_device = Some(data_device_manager.get_data_device_with_data(
+                queue_handle,
+                &seat,
+                DataDeviceData::default(),
+            ));
+        }
+
+        // Let the pointer start the drags.
+        if let Some(pointer) = seat_state.pointer.as_ref() {
+            pointer
+                .pointer()
+                .winit_data()
+                .set_data_device(seat_state.data_device.clone());
+        }
     }
 
     fn remove_capability(
@@ -163,6 +257,10 @@ impl SeatHandler for WinitState {
                     relative_pointer.destroy();
                 }
 
+                if let Some(pinch_gesture) = seat_state.pinch_gesture.take() {
+                    pinch_gesture.destroy();
+                }
+
                 if let Some(pointer) = seat_state.pointer.take() {
                     let pointer_data = pointer.pointer().winit_data();
 
@@ -173,6 +271,7 @@ impl SeatHandler for WinitState {
                     // Remove the inner locks/confines before dropping the pointer.
                     pointer_data.unlock_pointer();
                     pointer_data.unconfine_pointer();
+                    pointer_data.destroy_cursor_shape_device();
 
                     if pointer.pointer().version() >= 3 {
                         pointer.pointer().release();
@@ -205,7 +304,24 @@ impl SeatHandler for WinitState {
         _queue_handle: &QueueHandle<Self>,
         seat: WlSeat,
     ) {
-        let _ = self.seats.remove(&seat.id());
+        let seat_state = match self.seats.remove(&seat.id()) {
+            Some(seat_state) => seat_state,
+            None => return,
+        };
+
+        if let Some(tablet_seat) = seat_state.tablet_seat {
+            tablet_seat.destroy();
+        }
+
+        if let Some(selection_offer) = seat_state.selection_offer {
+            selection_offer.destroy();
+        }
+
+        if let Some(data_device) = seat_state.data_device {
+            if data_device.version() >= 2 {
+                data_device.release();
+            }
+        }
     }
 }
 
@@ -4,14 +4,21 @@ use std::ops::Deref;
 use std::sync::{Arc, Mutex};
 
 use sctk::reexports::client::delegate_dispatch;
-use sctk::reexports::client::protocol::wl_pointer::WlPointer;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_data_device::WlDataDevice;
+use sctk::reexports::client::protocol::wl_pointer::{AxisSource, WlPointer};
+use sctk::reexports::client::protocol::wl_region::WlRegion;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
-use sctk::reexports::client::{Connection, Proxy, QueueHandle, Dispatch};
-use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_confined_pointer_v1::ZwpConfinedPointerV1;
-use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_locked_pointer_v1::ZwpLockedPointerV1;
-use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_pointer_constraints_v1::{Lifetime, ZwpPointerConstraintsV1};
-use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::{Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::cursor_shape::v1::client::wp_cursor_shape_device_v1::WpCursorShapeDeviceV1;
+use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_confined_pointer_v1::{
+    self, ZwpConfinedPointerV1,
+};
+use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_locked_pointer_v1::{
+    self, ZwpLockedPointerV1,
+};
+use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_pointer_constraints_v1::ZwpPointerConstraintsV1;
 
 use sctk::compositor::SurfaceData;
 use sctk::globals::GlobalData;
@@ -24,8 +31,10 @@ use crate::dpi::{LogicalPosition, PhysicalPosition};
 use crate::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent};
 
 use crate::platform_impl::wayland::state::WinitState;
-use crate::platform_impl::wayland::{self, DeviceId, WindowId};
+use crate::platform_impl::wayland::{self, CursorGrabLifetime, DeviceId, WindowId};
 
+pub mod cursor_shape;
+pub mod pointer_gesture;
 pub mod relative_pointer;
 
 impl PointerHandler for WinitState {
@@ -181,6 +190,7 @@ impl PointerHandler for WinitState {
                 PointerEventKind::Axis {
                     horizontal,
                     vertical,
+                    source,
                     ..
                 } => {
                     // Get the current phase.
@@ -188,16 +198,27 @@ impl PointerHandler for WinitState {
 
                     let has_discrete_scroll = horizontal.discrete != 0 || vertical.discrete != 0;
 
-                    // Figure out what to do about start/ended phases here.
-                    //
-                    // Figure out how to deal with `Started`. Also the `Ended` is not guaranteed
-                    // to be sent for mouse wheels.
+                    // The source is sent with the first frame of the scroll, so keep it.
+                    if source.is_some() {
+                        pointer_data.axis_source = source;
+                    }
+
+                    // The wheels don't send the axis stop, so they never start a gesture.
+                    let is_wheel = matches!(
+                        pointer_data.axis_source,
+                        Some(AxisSource::Wheel | AxisSource::WheelTilt)
+                    );
+
+                    // The fingers and continuous sources start the gesture with the first frame
+                    // and end it with the axis stop, where kinetic scrolling could start.
                     let phase = if horizontal.stop || vertical.stop {
+                        // The next scroll sends its own source, so don't carry over this one.
+                        pointer_data.axis_source = None;
                         TouchPhase::Ended
                     } else {
                         match pointer_data.phase {
                             // Descrete scroll only results in moved events.
-                            _ if has_discrete_scroll => TouchPhase::Moved,
+                            _ if has_discrete_scroll || is_wheel => TouchPhase::Moved,
                             TouchPhase::Started | TouchPhase::Moved => TouchPhase::Moved,
                             _ => TouchPhase::Started,
                         }
@@ -262,6 +283,7 @@ impl WinitPointerData {
         pointer_constraints: &PointerConstraintsState,
         surface: &WlSurface,
         pointer: &WlPointer,
+        lifetime: CursorGrabLifetime,
         queue_handle: &QueueHandle<WinitState>,
     ) {
         let mut inner = self.inner.lock().unwrap();
@@ -270,9 +292,9 @@ impl WinitPointerData {
                 surface,
                 pointer,
                 None,
-                Lifetime::Persistent,
+                lifetime.into(),
                 queue_handle,
-                GlobalData,
+                PointerConstraintData::new(surface, lifetime),
             ));
         }
     }
@@ -289,15 +311,17 @@ impl WinitPointerData {
         pointer_constraints: &PointerConstraintsState,
         surface: &WlSurface,
         pointer: &WlPointer,
+        region: Option<&WlRegion>,
+        lifetime: CursorGrabLifetime,
         queue_handle: &QueueHandle<WinitState>,
     ) {
         self.inner.lock().unwrap().confined_pointer = Some(pointer_constraints.confine_pointer(
             surface,
             pointer,
-            None,
-            Lifetime::Persistent,
+            region,
+            lifetime.into(),
             queue_handle,
-            GlobalData,
+            PointerConstraintData::new(surface, lifetime),
         ));
     }
 
@@ -308,6 +332,30 @@ impl WinitPointerData {
         }
     }
 
+    pub fn set_cursor_shape_device(&self, cursor_shape_device: WpCursorShapeDeviceV1) {
+        self.inner.lock().unwrap().cursor_shape_device = Some(cursor_shape_device);
+    }
+
+    pub fn destroy_cursor_shape_device(&self) {
+        if let Some(cursor_shape_device) = self.inner.lock().unwrap().cursor_shape_device.take() {
+            cursor_shape_device.destroy();
+        }
+    }
+
+    pub fn set_data_device(&self, data_device: Option<WlDataDevice>) {
+        self.inner.lock().unwrap().data_device = data_device;
+    }
+
+    /// The data device of the seat, if the compositor supports drag and drop.
+    pub fn data_device(&self) -> Option<WlDataDevice> {
+        self.inner.lock().unwrap().data_device.clone()
+    }
+
+    /// The cursor shape device, if the compositor supports drawing the cursor.
+    pub fn cursor_shape_device(&self) -> Option<WpCursorShapeDeviceV1> {
+        self.inner.lock().unwrap().cursor_shape_device.clone()
+    }
+
     /// Seat associated with this pointer.
     pub fn seat(&self) -> &WlSeat {
         self.sctk_data.seat()
@@ -361,6 +409,12 @@ pub struct WinitPointerDataInner {
     /// The associated confined pointer.
     confined_pointer: Option<ZwpConfinedPointerV1>,
 
+    /// The cursor shape device to let the compositor draw the cursor.
+    cursor_shape_device: Option<WpCursorShapeDeviceV1>,
+
+    /// The data device of the seat to start drags.
+    data_device: Option<WlDataDevice>,
+
     /// Serial of the last button event.
     latest_button_serial: u32,
 
@@ -369,6 +423,9 @@ pub struct WinitPointerDataInner {
 
     /// Current axis phase.
     phase: TouchPhase,
+
+    /// The source of the current scroll.
+    axis_source: Option<AxisSource>,
 }
 
 impl Drop for WinitPointerDataInner {
@@ -389,8 +446,11 @@ impl Default for WinitPointerDataInner {
             surface: None,
             locked_pointer: None,
             confined_pointer: None,
+            cursor_shape_device: None,
+            data_device: None,
             latest_button_serial: 0,
             phase: TouchPhase::Ended,
+            axis_source: None,
         }
     }
 }
@@ -456,31 +516,69 @@ impl Dispatch<ZwpPointerConstraintsV1, GlobalData, WinitState> for PointerConstr
     }
 }
 
-impl Dispatch<ZwpLockedPointerV1, GlobalData, WinitState> for PointerConstraintsState {
+/// The data of the pointer lock and confinement.
+pub struct PointerConstraintData {
+    /// The window the pointer is constrained to.
+    window_id: WindowId,
+
+    /// The lifetime the constraint was created with.
+    lifetime: CursorGrabLifetime,
+}
+
+impl PointerConstraintData {
+    fn new(surface: &WlSurface, lifetime: CursorGrabLifetime) -> Self {
+        Self {
+            window_id: wayland::make_wid(surface),
+            lifetime,
+        }
+    }
+}
+
+impl Dispatch<ZwpLockedPointerV1, PointerConstraintData, WinitState> for PointerConstraintsState {
     fn event(
-        _state: &mut WinitState,
+        state: &mut WinitState,
         _proxy: &ZwpLockedPointerV1,
-        _event: <ZwpLockedPointerV1 as wayland_client::Proxy>::Event,
-        _data: &GlobalData,
+        event: <ZwpLockedPointerV1 as wayland_client::Proxy>::Event,
+        data: &PointerConstraintData,
         _conn: &Connection,
         _qhandle: &QueueHandle<WinitState>,
     ) {
+        // The persistent locks are reactivated by the compositor.
+        if let zwp_locked_pointer_v1::Event::Unlocked = event {
+            if data.lifetime != CursorGrabLifetime::OneShot {
+                return;
+            }
+
+            if let Some(window) = state.windows.get_mut().get(&data.window_id) {
+                window.lock().unwrap().cursor_grab_released();
+            }
+        }
     }
 }
 
-impl Dispatch<ZwpConfinedPointerV1, GlobalData, WinitState> for PointerConstraintsState {
+impl Dispatch<ZwpConfinedPointerV1, PointerConstraintData, WinitState> for PointerConstraintsState {
     fn event(
-        _state: &mut WinitState,
+        state: &mut WinitState,
         _proxy: &ZwpConfinedPointerV1,
-        _event: <ZwpConfinedPointerV1 as wayland_client::Proxy>::Event,
-        _data: &GlobalData,
+        event: <ZwpConfinedPointerV1 as wayland_client::Proxy>::Event,
+        data: &PointerConstraintData,
         _conn: &Connection,
         _qhandle: &QueueHandle<WinitState>,
     ) {
+        // The persistent confinements are reactivated by the compositor.
+        if let zwp_confined_pointer_v1::Event::Unconfined = event {
+            if data.lifetime != CursorGrabLifetime::OneShot {
+                return;
+            }
+
+            if let Some(window) = state.windows.get_mut().get(&data.window_id) {
+                window.lock().unwrap().cursor_grab_released();
+            }
+        }
     }
 }
 
 delegate_dispatch!(WinitState: [ WlPointer: WinitPointerData] => SeatState);
 delegate_dispatch!(WinitState: [ZwpPointerConstraintsV1: GlobalData] => PointerConstraintsState);
-delegate_dispatch!(WinitState: [ZwpLockedPointerV1: GlobalData] => PointerConstraintsState);
-delegate_dispatch!(WinitState: [ZwpConfinedPointerV1: GlobalData] => PointerConstraintsState);
+delegate_dispatch!(WinitState: [ZwpLockedPointerV1: PointerConstraintData] => PointerConstraintsState);
+delegate_dispatch!(WinitState: [ZwpConfinedPointerV1: PointerConstraintData] => PointerConstraintsState);
@@ -0,0 +1,155 @@
+//! Pointer gestures.
+
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::{delegate_dispatch, Dispatch};
+use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::pointer_gestures::zv1::{
+    client::zwp_pointer_gesture_pinch_v1::{self, ZwpPointerGesturePinchV1},
+    client::zwp_pointer_gestures_v1::ZwpPointerGesturesV1,
+};
+
+use sctk::compositor::SurfaceData;
+use sctk::globals::GlobalData;
+
+use crate::event::{TouchPhase, WindowEvent};
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+/// Wrapper around the pointer gestures.
+pub struct PointerGesturesState {
+    manager: ZwpPointerGesturesV1,
+}
+
+impl PointerGesturesState {
+    /// Create new pointer gestures manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+}
+
+impl Deref for PointerGesturesState {
+    type Target = ZwpPointerGesturesV1;
+
+    fn deref(&self) -> &Self::Target {
+        &self.manager
+    }
+}
+
+/// The data associated with the pinch gesture.
+#[derive(Debug, Default)]
+pub struct PinchGestureData {
+    inner: Mutex<PinchGestureDataInner>,
+}
+
+#[derive(Debug, Default)]
+struct PinchGestureDataInner {
+    /// The window the gesture has started on.
+    window_id: Option<WindowId>,
+
+    /// The scale reported by the previous update, since winit reports deltas.
+    previous_scale: f64,
+}
+
+impl Dispatch<ZwpPointerGesturesV1, GlobalData, WinitState> for PointerGesturesState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpPointerGesturesV1,
+        _event: <ZwpPointerGesturesV1 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpPointerGesturePinchV1, PinchGestureData, WinitState> for PointerGesturesState {
+    fn event(
+        state: &mut WinitState,
+        _proxy: &ZwpPointerGesturePinchV1,
+        event: <ZwpPointerGesturePinchV1 as Proxy>::Event,
+        data: &PinchGestureData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        let device_id =
+            crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(wayland::DeviceId));
+
+        let (window_id, magnify, rotate, phase) = match event {
+            zwp_pointer_gesture_pinch_v1::Event::Begin { surface, .. } => {
+                // The gesture could start on the decorations.
+                let parent_surface = match surface.data::<SurfaceData>() {
+                    Some(data) => data.parent_surface().unwrap_or(&surface),
+                    None => return,
+                };
+
+                let window_id = wayland::make_wid(parent_surface);
+                inner.window_id = Some(window_id);
+                inner.previous_scale = 1.;
+
+                (window_id, 0., 0., TouchPhase::Started)
+            }
+            zwp_pointer_gesture_pinch_v1::Event::Update {
+                scale, rotation, ..
+            } => {
+                let window_id = match inner.window_id {
+                    Some(window_id) => window_id,
+                    None => return,
+                };
+
+                let magnify = scale - inner.previous_scale;
+                inner.previous_scale = scale;
+
+                // Wayland reports clockwise rotation, while winit uses counterclockwise.
+                (window_id, magnify, -rotation as f32, TouchPhase::Moved)
+            }
+            zwp_pointer_gesture_pinch_v1::Event::End { cancelled, .. } => {
+                let window_id = match inner.window_id.take() {
+                    Some(window_id) => window_id,
+                    None => return,
+                };
+
+                let phase = if cancelled == 0 {
+                    TouchPhase::Ended
+                } else {
+                    TouchPhase::Cancelled
+                };
+
+                (window_id, 0., 0., phase)
+            }
+            _ => return,
+        };
+
+        // Ensure that window exists.
+        if !state.windows.get_mut().contains_key(&window_id) {
+            return;
+        }
+
+        state.events_sink.push_window_event(
+            WindowEvent::TouchpadMagnify {
+                device_id,
+                delta: magnify,
+                phase,
+            },
+            window_id,
+        );
+        state.events_sink.push_window_event(
+            WindowEvent::TouchpadRotate {
+                device_id,
+                delta: rotate,
+                phase,
+            },
+            window_id,
+        );
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpPointerGesturesV1: GlobalData] => PointerGesturesState);
+delegate_dispatch!(WinitState: [ZwpPointerGesturePinchV1: PinchGestureData] => PointerGesturesState);
@@ -0,0 +1,397 @@
+//! Tablet handling.
+
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use sctk::compositor::SurfaceData;
+use sctk::globals::GlobalData;
+use sctk::reexports::client::{Connection, Proxy, QueueHandle, WEnum};
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{delegate_dispatch, event_created_child, Dispatch};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_manager_v2::ZwpTabletManagerV2;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_group_v2::{
+    self, ZwpTabletPadGroupV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_ring_v2::ZwpTabletPadRingV2;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_strip_v2::ZwpTabletPadStripV2;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_v2::{
+    self, ZwpTabletPadV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_seat_v2::{
+    self, ZwpTabletSeatV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_tool_v2::{
+    ButtonState, Event as TabletToolEvent, Type as ToolType, ZwpTabletToolV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_v2::{self, ZwpTabletV2};
+
+use crate::dpi::LogicalPosition;
+use crate::event::{Force, Touch, TouchPhase, WindowEvent};
+
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, DeviceId};
+
+/// The maximum pressure reported by the tablet tool.
+const MAX_PRESSURE: f64 = 65535.;
+
+/// The tag of the touch ids of the tablet tools, which keeps them above the 32-bit ids of the
+/// `wl_touch` points sharing the same device.
+const TOOL_ID_TAG: u64 = 1 << 32;
+
+/// The touch id the tablet tool is reported with.
+fn tool_touch_id(tool: &ZwpTabletToolV2) -> u64 {
+    TOOL_ID_TAG | tool.id().protocol_id() as u64
+}
+
+/// The physical type of the tablet tool.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TabletToolType {
+    /// A pen, which is also used for the tools of an unknown type.
+    #[default]
+    Pen,
+
+    /// The eraser end of a pen.
+    Eraser,
+
+    /// A paintbrush-like tool.
+    Brush,
+
+    /// A physical pencil.
+    Pencil,
+
+    /// An airbrush-like tool.
+    Airbrush,
+
+    /// A finger on a touch tablet.
+    Finger,
+
+    /// A mouse bound to the tablet.
+    Mouse,
+
+    /// A mouse-like lens bound to the tablet.
+    Lens,
+}
+
+impl From<ToolType> for TabletToolType {
+    fn from(tool_type: ToolType) -> Self {
+        match tool_type {
+            ToolType::Eraser => Self::Eraser,
+            ToolType::Brush => Self::Brush,
+            ToolType::Pencil => Self::Pencil,
+            ToolType::Airbrush => Self::Airbrush,
+            ToolType::Finger => Self::Finger,
+            ToolType::Mouse => Self::Mouse,
+            ToolType::Lens => Self::Lens,
+            _ => Self::Pen,
+        }
+    }
+}
+
+/// The state of a tablet tool in proximity of the window.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TabletToolState {
+    /// The physical type of the tool.
+    pub tool_type: TabletToolType,
+
+    /// The tilt of the tool along the X and Y axes of the tablet in degrees, in the range of -90
+    /// to 90.
+    pub tilt: (f64, f64),
+
+    /// The pressed buttons of the tool, as Linux input event codes like `BTN_STYLUS`.
+    pub buttons: Vec<u32>,
+}
+
+pub struct TabletManagerState {
+    tablet_manager: ZwpTabletManagerV2,
+}
+
+impl TabletManagerState {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let tablet_manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { tablet_manager })
+    }
+}
+
+impl Deref for TabletManagerState {
+    type Target = ZwpTabletManagerV2;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tablet_manager
+    }
+}
+
+/// The data associated with the tablet tool.
+#[derive(Debug, Default)]
+pub struct TabletToolData {
+    inner: Mutex<TabletToolDataInner>,
+}
+
+#[derive(Debug, Default)]
+struct TabletToolDataInner {
+    /// The window surface the tool is in proximity of.
+    surface: Option<WlSurface>,
+
+    /// The state of the tool shared with the window.
+    tool_state: TabletToolState,
+
+    /// The location of the tool on the surface.
+    location: LogicalPosition<f64>,
+
+    /// The pressure of the tool.
+    pressure: f64,
+
+    /// Whether the tool is touching the tablet.
+    is_down: bool,
+
+    /// The phase which will be reported on `frame`.
+    pending_phase: Option<TouchPhase>,
+
+    /// Whether the tool has left the surface.
+    pending_proximity_out: bool,
+}
+
+impl Dispatch<ZwpTabletManagerV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletManagerV2,
+        _event: <ZwpTabletManagerV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpTabletSeatV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletSeatV2,
+        _event: <ZwpTabletSeatV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        // The tablets, tools, and pads are handled on their own.
+    }
+
+    event_created_child!(WinitState, ZwpTabletSeatV2, [
+        zwp_tablet_seat_v2::EVT_TABLET_ADDED_OPCODE => (ZwpTabletV2, GlobalData),
+        zwp_tablet_seat_v2::EVT_TOOL_ADDED_OPCODE => (ZwpTabletToolV2, TabletToolData::default()),
+        zwp_tablet_seat_v2::EVT_PAD_ADDED_OPCODE => (ZwpTabletPadV2, GlobalData),
+    ]);
+}
+
+impl Dispatch<ZwpTabletV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        proxy: &ZwpTabletV2,
+        event: <ZwpTabletV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        if let zwp_tablet_v2::Event::Removed = event {
+            proxy.destroy();
+        }
+    }
+}
+
+impl Dispatch<ZwpTabletToolV2, TabletToolData, WinitState> for TabletManagerState {
+    fn event(
+        state: &mut WinitState,
+        tool: &ZwpTabletToolV2,
+        event: <ZwpTabletToolV2 as Proxy>::Event,
+        data: &TabletToolData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut tool_data = data.inner.lock().unwrap();
+        match event {
+            TabletToolEvent::Type {
+                tool_type: WEnum::Value(tool_type),
+            } => {
+                tool_data.tool_state.tool_type = tool_type.into();
+            }
+            TabletToolEvent::ProximityIn { surface, .. } => {
+                // The tool could be over the decorations.
+                let surface = match surface.data::<SurfaceData>() {
+                    Some(data) => data.parent_surface().cloned().unwrap_or(surface),
+                    None => return,
+                };
+                tool_data.surface = Some(surface);
+            }
+            TabletToolEvent::ProximityOut => {
+                if tool_data.is_down {
+                    tool_data.pending_phase = Some(TouchPhase::Cancelled);
+                }
+                tool_data.pending_proximity_out = true;
+            }
+            TabletToolEvent::Down { .. } => {
+                tool_data.is_down = true;
+                tool_data.pending_phase = Some(TouchPhase::Started);
+            }
+            TabletToolEvent::Up if tool_data.is_down => {
+                tool_data.pending_phase = Some(TouchPhase::Ended);
+            }
+            TabletToolEvent::Motion { x, y } => {
+                tool_data.location = LogicalPosition::new(x, y);
+                if tool_data.is_down && tool_data.pending_phase.is_none() {
+                    tool_data.pending_phase = Some(TouchPhase::Moved);
+                }
+            }
+            TabletToolEvent::Pressure { pressure } => {
+                tool_data.pressure = pressure as f64 / MAX_PRESSURE;
+                if tool_data.is_down && tool_data.pending_phase.is_none() {
+                    tool_data.pending_phase = Some(TouchPhase::Moved);
+                }
+            }
+            TabletToolEvent::Tilt { tilt_x, tilt_y } => {
+                tool_data.tool_state.tilt = (tilt_x, tilt_y);
+                if tool_data.is_down && tool_data.pending_phase.is_none() {
+                    tool_data.pending_phase = Some(TouchPhase::Moved);
+                }
+            }
+            TabletToolEvent::Button {
+                button,
+                state: WEnum::Value(button_state),
+                ..
+            } => {
+                let buttons = &mut tool_data.tool_state.buttons;
+                buttons.retain(|&pressed| pressed != button);
+                if button_state == ButtonState::Pressed {
+                    buttons.push(button);
+                }
+
+                if tool_data.is_down && tool_data.pending_phase.is_none() {
+                    tool_data.pending_phase = Some(TouchPhase::Moved);
+                }
+            }
+            TabletToolEvent::Frame { .. } => {
+                let id = tool_touch_id(tool);
+                let window_id = tool_data.surface.as_ref().map(wayland::make_wid);
+
+                // The state is available to the window while the tool is in proximity.
+                let scale_factor = window_id
+                    .and_then(|window_id| state.windows.get_mut().get(&window_id))
+                    .map(|window| {
+                        let mut window = window.lock().unwrap();
+                        if tool_data.pending_proximity_out {
+                            window.tablet_tool_left(id);
+                        } else {
+                            window.tablet_tool_changed(id, tool_data.tool_state.clone());
+                        }
+                        window.scale_factor()
+                    });
+
+                if let Some(phase) = tool_data.pending_phase.take() {
+                    if let (Some(window_id), Some(scale_factor)) = (window_id, scale_factor) {
+                        state.events_sink.push_window_event(
+                            WindowEvent::Touch(Touch {
+                                device_id: crate::event::DeviceId(
+                                    crate::platform_impl::DeviceId::Wayland(DeviceId),
+                                ),
+                                phase,
+                                location: tool_data.location.to_physical(scale_factor),
+                                force: Some(Force::Normalized(tool_data.pressure)),
+                                id,
+                            }),
+                            window_id,
+                        );
+                    }
+
+                    if matches!(phase, TouchPhase::Ended | TouchPhase::Cancelled) {
+                        tool_data.is_down = false;
+                    }
+                }
+
+                if tool_data.pending_proximity_out {
+                    tool_data.pending_proximity_out = false;
+                    tool_data.is_down = false;
+                    tool_data.surface = None;
+                    tool_data.tool_state.buttons.clear();
+                }
+            }
+            TabletToolEvent::Removed => {
+                tool.destroy();
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Dispatch<ZwpTabletPadV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        proxy: &ZwpTabletPadV2,
+        event: <ZwpTabletPadV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        if let zwp_tablet_pad_v2::Event::Removed = event {
+            proxy.destroy();
+        }
+    }
+
+    event_created_child!(WinitState, ZwpTabletPadV2, [
+        zwp_tablet_pad_v2::EVT_GROUP_OPCODE => (ZwpTabletPadGroupV2, GlobalData),
+    ]);
+}
+
+impl Dispatch<ZwpTabletPadGroupV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletPadGroupV2,
+        _event: <ZwpTabletPadGroupV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        // Pads are not handled.
+    }
+
+    event_created_child!(WinitState, ZwpTabletPadGroupV2, [
+        zwp_tablet_pad_group_v2::EVT_RING_OPCODE => (ZwpTabletPadRingV2, GlobalData),
+        zwp_tablet_pad_group_v2::EVT_STRIP_OPCODE => (ZwpTabletPadStripV2, GlobalData),
+    ]);
+}
+
+impl Dispatch<ZwpTabletPadRingV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletPadRingV2,
+        _event: <ZwpTabletPadRingV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        // Pads are not handled.
+    }
+}
+
+impl Dispatch<ZwpTabletPadStripV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletPadStripV2,
+        _event: <ZwpTabletPadStripV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        // Pads are not handled.
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpTabletManagerV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletSeatV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletToolV2: TabletToolData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadGroupV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadRingV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadStripV2: GlobalData] => TabletManagerState);
@@ -10,7 +10,7 @@ use sctk::reexports::client::Dispatch;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_manager_v3::ZwpTextInputManagerV3;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::Event as TextInputEvent;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::{
-    ContentHint, ContentPurpose, ZwpTextInputV3,
+    ChangeCause, ContentHint, ContentPurpose, ZwpTextInputV3,
 };
 
 use crate::event::{Ime, WindowEvent};
@@ -76,6 +76,9 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 if window.ime_allowed() {
                     text_input.enable();
                     text_input.set_content_type_by_purpose(window.ime_purpose());
+                    if let Some(surrounding_text) = window.ime_surrounding_text() {
+                        text_input.set_surrounding_text_with(surrounding_text);
+                    }
                     text_input.commit();
                     state
                         .events_sink
@@ -126,7 +129,7 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 })
             }
             TextInputEvent::CommitString { text } => {
-                text_input_data.pending_preedit = None;
+                // The preedit of the same batch is shown after the commit, so it's kept.
                 text_input_data.pending_commit = text;
             }
             TextInputEvent::Done { .. } => {
@@ -150,9 +153,11 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
 
                 // Send preedit.
                 if let Some(preedit) = text_input_data.pending_preedit.take() {
-                    let cursor_range = preedit
-                        .cursor_begin
-                        .map(|b| (b, preedit.cursor_end.unwrap_or(b)));
+                    // The selected part of the preedit is between the cursor positions.
+                    let cursor_range = preedit.cursor_begin.map(|begin| {
+                        let end = preedit.cursor_end.unwrap_or(begin);
+                        (begin.min(end), begin.max(end))
+                    });
 
                     state.events_sink.push_window_event(
                         WindowEvent::Ime(Ime::Preedit(preedit.text, cursor_range)),
@@ -170,17 +175,83 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
 
 pub trait ZwpTextInputV3Ext {
     fn set_content_type_by_purpose(&self, purpose: ImePurpose);
+
+    fn set_surrounding_text_with(&self, surrounding_text: &SurroundingText);
 }
 
 impl ZwpTextInputV3Ext for ZwpTextInputV3 {
     fn set_content_type_by_purpose(&self, purpose: ImePurpose) {
         let (hint, purpose) = match purpose {
             ImePurpose::Normal => (ContentHint::None, ContentPurpose::Normal),
-            ImePurpose::Password => (ContentHint::SensitiveData, ContentPurpose::Password),
+            // Hide the text from the IME and keep it from learning the password.
+            ImePurpose::Password => (
+                ContentHint::HiddenText | ContentHint::SensitiveData,
+                ContentPurpose::Password,
+            ),
             ImePurpose::Terminal => (ContentHint::None, ContentPurpose::Terminal),
         };
         self.set_content_type(hint, purpose);
     }
+
+    fn set_surrounding_text_with(&self, surrounding_text: &SurroundingText) {
+        self.set_surrounding_text(
+            surrounding_text.text.clone(),
+            surrounding_text.cursor as i32,
+            surrounding_text.anchor as i32,
+        );
+        self.set_text_change_cause(ChangeCause::Other);
+    }
+}
+
+/// The maximum length of the surrounding text in bytes, so it fits into the protocol message.
+const MAX_SURROUNDING_TEXT_LEN: usize = 4000;
+
+/// The text around the cursor, with the cursor and the anchor of the selection as byte offsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurroundingText {
+    text: String,
+    cursor: usize,
+    anchor: usize,
+}
+
+impl SurroundingText {
+    /// Create the surrounding text, trimming it around the cursor when it doesn't fit into the
+    /// protocol message.
+    pub fn new(mut text: String, cursor: usize, anchor: usize) -> Self {
+        let cursor = cursor.min(text.len());
+        let anchor = anchor.min(text.len());
+
+        let mut start = 0;
+        if text.len() > MAX_SURROUNDING_TEXT_LEN {
+            // Keep the selection when it fits, otherwise the text around the cursor.
+            let (selection_start, selection_end) = (cursor.min(anchor), cursor.max(anchor));
+            let selection_len = selection_end - selection_start;
+            start = if selection_len <= MAX_SURROUNDING_TEXT_LEN {
+                selection_start.saturating_sub((MAX_SURROUNDING_TEXT_LEN - selection_len) / 2)
+            } else {
+                cursor.saturating_sub(MAX_SURROUNDING_TEXT_LEN / 2)
+            };
+            start = start.min(text.len() - MAX_SURROUNDING_TEXT_LEN);
+
+            let mut end = start + MAX_SURROUNDING_TEXT_LEN;
+            while !text.is_char_boundary(start) {
+                start += 1;
+            }
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            text.truncate(end);
+            text.drain(..start);
+        }
+
+        let len = text.len();
+        Self {
+            text,
+            cursor: cursor.saturating_sub(start).min(len),
+            anchor: anchor.saturating_sub(start).min(len),
+        }
+    }
 }
 
 /// The Data associated with the text input.
@@ -19,7 +19,7 @@ impl TouchHandler for WinitState {
         _: &Connection,
         _: &QueueHandle<Self>,
         touch: &WlTouch,
-        _: u32,
+        serial: u32,
         _: u32,
         surface: WlSurface,
         id: i32,
@@ -27,7 +27,12 @@ impl TouchHandler for WinitState {
     ) {
         let window_id = wayland::make_wid(&surface);
         let scale_factor = match self.windows.get_mut().get(&window_id) {
-            Some(window) => window.lock().unwrap().scale_factor(),
+            Some(window) => {
+                let mut window = window.lock().unwrap();
+                // Let the touch move and resize the window.
+                window.set_touch_down(Some((touch.seat().clone(), serial)));
+                window.scale_factor()
+            }
             None => return,
         };
 
@@ -72,8 +77,18 @@ impl TouchHandler for WinitState {
         };
 
         let window_id = wayland::make_wid(&touch_point.surface);
+        let has_points = seat_state
+            .touch_map
+            .values()
+            .any(|point| point.surface == touch_point.surface);
         let scale_factor = match self.windows.get_mut().get(&window_id) {
-            Some(window) => window.lock().unwrap().scale_factor(),
+            Some(window) => {
+                let mut window = window.lock().unwrap();
+                if !has_points {
+                    window.set_touch_down(None);
+                }
+                window.scale_factor()
+            }
             None => return,
         };
 
@@ -136,7 +151,11 @@ impl TouchHandler for WinitState {
         for (id, touch_point) in seat_state.touch_map.drain() {
             let window_id = wayland::make_wid(&touch_point.surface);
             let scale_factor = match self.windows.get_mut().get(&window_id) {
-                Some(window) => window.lock().unwrap().scale_factor(),
+                Some(window) => {
+                    let mut window = window.lock().unwrap();
+                    window.set_touch_down(None);
+                    window.scale_factor()
+                }
                 None => return,
             };
 
@@ -10,8 +10,10 @@ use sctk::reexports::client::globals::GlobalList;
 use sctk::reexports::client::protocol::wl_output::WlOutput;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::ext::foreign_toplevel_list::v1::client::ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1;
 
 use sctk::compositor::{CompositorHandler, CompositorState};
+use sctk::data_device_manager::DataDeviceManagerState;
 use sctk::output::{OutputHandler, OutputState};
 use sctk::registry::{ProvidesRegistryState, RegistryState};
 use sctk::seat::pointer::ThemedPointer;
@@ -27,10 +29,14 @@ use crate::dpi::LogicalSize;
 use super::event_loop::sink::EventSink;
 use super::output::MonitorHandle;
 use super::seat::{
-    PointerConstraintsState, RelativePointerState, TextInputState, WinitPointerData,
-    WinitPointerDataExt, WinitSeatState,
+    CursorShapeManager, PointerConstraintsState, PointerGesturesState, RelativePointerState,
+    TabletManagerState, TextInputState, WinitPointerData, WinitPointerDataExt, WinitSeatState,
 };
+use super::types::ext_foreign_toplevel_list::ForeignToplevelListState;
+use super::types::wp_content_type::ContentTypeManager;
 use super::types::wp_fractional_scaling::FractionalScalingManager;
+use super::types::wp_idle_inhibit::IdleInhibitManager;
+use super::types::wp_single_pixel_buffer::SinglePixelBufferManager;
 use super::types::wp_viewporter::ViewporterState;
 use super::types::xdg_activation::XdgActivationState;
 use super::window::{WindowRequests, WindowState};
@@ -74,9 +80,22 @@ pub struct WinitState {
     /// Currently handled seats.
     pub seats: FnvHashMap<ObjectId, WinitSeatState>,
 
+    /// The cursor theme name and base size requested by the user, instead of the system one.
+    pub cursor_theme: Option<(String, u32)>,
+
+    /// The preferred color scheme of the system, `None` without a preference.
+    #[cfg(feature = "wayland-color-scheme")]
+    pub system_theme: Option<crate::window::Theme>,
+
     /// Currently present cursor surfaces.
     pub pointer_surfaces: FnvHashMap<ObjectId, Arc<ThemedPointer<WinitPointerData>>>,
 
+    /// The tablet manager to handle the stylus input.
+    pub tablet_manager: Option<TabletManagerState>,
+
+    /// The data device manager to handle drag and drop.
+    pub data_device_manager: Option<DataDeviceManagerState>,
+
     /// The state of the text input on the client.
     pub text_input_state: Option<TextInputState>,
 
@@ -96,12 +115,33 @@ pub struct WinitState {
     /// Pointer constraints to handle pointer locking and confining.
     pub pointer_constraints: Option<Arc<PointerConstraintsState>>,
 
+    /// Cursor shape manager to let the compositor draw the cursors.
+    pub cursor_shape_manager: Option<CursorShapeManager>,
+
+    /// Pointer gestures to handle touchpad pinch gestures.
+    pub pointer_gestures: Option<PointerGesturesState>,
+
     /// Viewporter state on the given window.
     pub viewporter_state: Option<ViewporterState>,
 
     /// Fractional scaling manager.
     pub fractional_scaling_manager: Option<FractionalScalingManager>,
 
+    /// Content type manager to hint the kind of the window content.
+    pub content_type_manager: Option<ContentTypeManager>,
+
+    /// Single pixel buffer manager to fill windows with a solid color.
+    pub single_pixel_buffer_manager: Option<Arc<SinglePixelBufferManager>>,
+
+    /// Idle inhibit manager to prevent screen blanking.
+    pub idle_inhibit_manager: Option<Arc<IdleInhibitManager>>,
+
+    /// Foreign toplevel list to observe windows of other clients.
+    pub foreign_toplevel_list: Option<ForeignToplevelListState>,
+
+    /// Toplevels announced by the foreign toplevel list.
+    pub foreign_toplevels: Vec<ExtForeignToplevelHandleV1>,
+
     /// Loop handle to re-register event sources, such as keyboard repeat.
     pub loop_handle: LoopHandle<'static, Self>,
 }
@@ -154,15 +194,31 @@ impl WinitState {
             window_events_sink: Default::default(),
             viewporter_state,
             fractional_scaling_manager,
+            content_type_manager: ContentTypeManager::new(globals, queue_handle).ok(),
+            single_pixel_buffer_manager: SinglePixelBufferManager::new(globals, queue_handle)
+                .map(Arc::new)
+                .ok(),
+            idle_inhibit_manager: IdleInhibitManager::new(globals, queue_handle)
+                .map(Arc::new)
+                .ok(),
+            foreign_toplevel_list: ForeignToplevelListState::bind(globals, queue_handle).ok(),
+            foreign_toplevels: Vec::new(),
 
             seats,
             text_input_state: TextInputState::new(globals, queue_handle).ok(),
+            data_device_manager: DataDeviceManagerState::bind(globals, queue_handle).ok(),
+            tablet_manager: TabletManagerState::new(globals, queue_handle).ok(),
 
             relative_pointer: RelativePointerState::new(globals, queue_handle).ok(),
             pointer_constraints: PointerConstraintsState::new(globals, queue_handle)
                 .map(Arc::new)
                 .ok(),
+            cursor_shape_manager: CursorShapeManager::new(globals, queue_handle).ok(),
+            pointer_gestures: PointerGesturesState::new(globals, queue_handle).ok(),
             pointer_surfaces: Default::default(),
+            cursor_theme: None,
+            #[cfg(feature = "wayland-color-scheme")]
+            system_theme: None,
 
             monitors: Arc::new(Mutex::new(monitors)),
             events_sink: EventSink::new(),
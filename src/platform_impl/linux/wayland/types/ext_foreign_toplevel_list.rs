@@ -0,0 +1,164 @@
+//! Handling of the foreign toplevel list, which is used to observe windows of other clients.
+
+use std::sync::Mutex;
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{
+    delegate_dispatch, event_created_child, Connection, Proxy, QueueHandle,
+};
+use sctk::reexports::protocols::ext::foreign_toplevel_list::v1::client::ext_foreign_toplevel_handle_v1::{
+    Event as ForeignToplevelHandleEvent, ExtForeignToplevelHandleV1,
+};
+use sctk::reexports::protocols::ext::foreign_toplevel_list::v1::client::ext_foreign_toplevel_list_v1::{
+    self, Event as ForeignToplevelListEvent, ExtForeignToplevelListV1,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::platform_impl::wayland::event_loop::EventLoopWindowTarget;
+use crate::platform_impl::wayland::state::WinitState;
+
+impl<T> EventLoopWindowTarget<T> {
+    #[inline]
+    pub fn foreign_toplevels(&self) -> Vec<ForeignToplevel> {
+        self.state
+            .borrow()
+            .foreign_toplevels
+            .iter()
+            .filter_map(|handle| {
+                let data = handle.data::<ForeignToplevelData>()?;
+                data.inner.lock().unwrap().current.clone()
+            })
+            .collect()
+    }
+}
+
+/// The foreign toplevel list.
+#[derive(Debug)]
+pub struct ForeignToplevelListState {
+    _list: ExtForeignToplevelListV1,
+}
+
+impl ForeignToplevelListState {
+    /// Bind the foreign toplevel list, which will immediately start sending toplevels.
+    pub fn bind(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let list = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { _list: list })
+    }
+}
+
+/// A toplevel window of some client, which is reported by the compositor.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ForeignToplevel {
+    identifier: String,
+    title: String,
+    app_id: String,
+}
+
+impl ForeignToplevel {
+    /// The identifier of the toplevel, which is unique and never reused by the compositor.
+    #[inline]
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// The title of the toplevel.
+    #[inline]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The application id of the toplevel.
+    #[inline]
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+}
+
+/// The data associated with the foreign toplevel handle.
+#[derive(Debug, Default)]
+pub struct ForeignToplevelData {
+    inner: Mutex<ForeignToplevelDataInner>,
+}
+
+#[derive(Debug, Default)]
+struct ForeignToplevelDataInner {
+    /// The state which will be applied on `done`.
+    pending: ForeignToplevel,
+
+    /// The state applied by the last `done`.
+    current: Option<ForeignToplevel>,
+}
+
+impl Dispatch<ExtForeignToplevelListV1, GlobalData, WinitState> for ForeignToplevelListState {
+    fn event(
+        state: &mut WinitState,
+        proxy: &ExtForeignToplevelListV1,
+        event: <ExtForeignToplevelListV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        match event {
+            ForeignToplevelListEvent::Toplevel { toplevel } => {
+                state.foreign_toplevels.push(toplevel);
+            }
+            ForeignToplevelListEvent::Finished => {
+                for handle in state.foreign_toplevels.drain(..) {
+                    handle.destroy();
+                }
+
+                // The compositor won't send any toplevels anymore.
+                if state.foreign_toplevel_list.take().is_some() {
+                    proxy.destroy();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    event_created_child!(WinitState, ExtForeignToplevelListV1, [
+        ext_foreign_toplevel_list_v1::EVT_TOPLEVEL_OPCODE => (ExtForeignToplevelHandleV1, ForeignToplevelData::default())
+    ]);
+}
+
+impl Dispatch<ExtForeignToplevelHandleV1, ForeignToplevelData, WinitState>
+    for ForeignToplevelListState
+{
+    fn event(
+        state: &mut WinitState,
+        proxy: &ExtForeignToplevelHandleV1,
+        event: <ExtForeignToplevelHandleV1 as Proxy>::Event,
+        data: &ForeignToplevelData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        match event {
+            ForeignToplevelHandleEvent::Identifier { identifier } => {
+                inner.pending.identifier = identifier;
+            }
+            ForeignToplevelHandleEvent::Title { title } => {
+                inner.pending.title = title;
+            }
+            ForeignToplevelHandleEvent::AppId { app_id } => {
+                inner.pending.app_id = app_id;
+            }
+            ForeignToplevelHandleEvent::Done => {
+                inner.current = Some(inner.pending.clone());
+            }
+            ForeignToplevelHandleEvent::Closed => {
+                state.foreign_toplevels.retain(|handle| handle != proxy);
+                proxy.destroy();
+            }
+            _ => (),
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [ExtForeignToplevelListV1: GlobalData] => ForeignToplevelListState);
+delegate_dispatch!(WinitState: [ExtForeignToplevelHandleV1: ForeignToplevelData] => ForeignToplevelListState);
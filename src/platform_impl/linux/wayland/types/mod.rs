@@ -1,5 +1,10 @@
 //! Wayland protocol implementation boilerplate.
 
+pub mod ext_foreign_toplevel_list;
+pub mod wl_subsurface;
+pub mod wp_content_type;
 pub mod wp_fractional_scaling;
+pub mod wp_idle_inhibit;
+pub mod wp_single_pixel_buffer;
 pub mod wp_viewporter;
 pub mod xdg_activation;
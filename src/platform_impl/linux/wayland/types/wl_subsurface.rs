@@ -0,0 +1,99 @@
+//! Handling of the subsurfaces, which are composited with the window by the compositor.
+
+use std::os::raw;
+
+use sctk::reexports::client::protocol::wl_subsurface::WlSubsurface;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{Proxy, QueueHandle};
+
+use sctk::compositor::Region;
+use sctk::subcompositor::SubcompositorState;
+
+use crate::dpi::LogicalPosition;
+use crate::platform_impl::wayland::state::WinitState;
+
+/// A surface composited with the window, e.g. to present video in a format the compositor could
+/// convert, while the window content is drawn above it.
+///
+/// The subsurface is destroyed when dropped.
+#[derive(Debug)]
+pub struct Subsurface {
+    subsurface: WlSubsurface,
+    surface: WlSurface,
+    parent: WlSurface,
+}
+
+impl Subsurface {
+    pub(crate) fn new(
+        subcompositor: &SubcompositorState,
+        parent: &WlSurface,
+        input_region: Option<&Region>,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Self {
+        let (subsurface, surface) = subcompositor.create_subsurface(parent.clone(), queue_handle);
+
+        // Surfaces with a parent are taken for the decorations by the pointer handling, so the
+        // input should fall through to the window. The region is copied by the compositor.
+        match input_region {
+            Some(region) => surface.set_input_region(Some(region.wl_region())),
+            None => warn!("Failed to set the input region of the subsurface."),
+        }
+        Self {
+            subsurface,
+            surface,
+            parent: parent.clone(),
+        }
+    }
+
+    /// Returns a pointer to the `wl_surface` of the subsurface, to attach the buffers to.
+    ///
+    /// The buffers are not scaled, so the buffer scale or the viewport of the surface should be
+    /// set by the client drawing into it.
+    #[inline]
+    pub fn wayland_surface(&self) -> *mut raw::c_void {
+        self.surface.id().as_ptr() as *mut _
+    }
+
+    /// Set the position of the subsurface relative to the top left corner of the window content.
+    ///
+    /// The position is applied with the next commit of the window.
+    #[inline]
+    pub fn set_position(&self, position: LogicalPosition<i32>) {
+        self.subsurface.set_position(position.x, position.y);
+    }
+
+    /// Place the subsurface above the window content.
+    ///
+    /// The placement is applied with the next commit of the window.
+    #[inline]
+    pub fn place_above_parent(&self) {
+        self.subsurface.place_above(&self.parent);
+    }
+
+    /// Place the subsurface below the window content, which should be transparent where the
+    /// subsurface should be seen. The subsurface is placed above the window by default.
+    ///
+    /// The placement is applied with the next commit of the window.
+    #[inline]
+    pub fn place_below_parent(&self) {
+        self.subsurface.place_below(&self.parent);
+    }
+
+    /// Set whether the commits of the subsurface are applied together with the next commit of the
+    /// window, which is the default, or right away.
+    #[inline]
+    pub fn set_sync(&self, sync: bool) {
+        if sync {
+            self.subsurface.set_sync();
+        } else {
+            self.subsurface.set_desync();
+        }
+    }
+}
+
+impl Drop for Subsurface {
+    fn drop(&mut self) {
+        self.subsurface.destroy();
+        self.surface.destroy();
+    }
+}
@@ -0,0 +1,99 @@
+//! Handling of the content type hint.
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::content_type::v1::client::wp_content_type_manager_v1::WpContentTypeManagerV1;
+use sctk::reexports::protocols::wp::content_type::v1::client::wp_content_type_v1::{
+    Type, WpContentTypeV1,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::platform_impl::wayland::state::WinitState;
+
+/// The kind of content displayed by the window, which the compositor may use to adjust its
+/// behavior, e.g. to allow tearing or to disable dimming.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentType {
+    /// No particular kind of content.
+    #[default]
+    None,
+
+    /// Still pictures, which may be presented with reduced changes.
+    Photo,
+
+    /// Video or animations, which may be synchronized to a constant rate.
+    Video,
+
+    /// Interactive content, which should be presented with low latency.
+    Game,
+}
+
+impl From<ContentType> for Type {
+    fn from(content_type: ContentType) -> Self {
+        match content_type {
+            ContentType::None => Type::None,
+            ContentType::Photo => Type::Photo,
+            ContentType::Video => Type::Video,
+            ContentType::Game => Type::Game,
+        }
+    }
+}
+
+/// Content type manager.
+#[derive(Debug)]
+pub struct ContentTypeManager {
+    manager: WpContentTypeManagerV1,
+}
+
+impl ContentTypeManager {
+    /// Create new content type manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    /// Get the content type object for the given surface.
+    pub fn content_type(
+        &self,
+        surface: &WlSurface,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> WpContentTypeV1 {
+        self.manager
+            .get_surface_content_type(surface, queue_handle, GlobalData)
+    }
+}
+
+impl Dispatch<WpContentTypeManagerV1, GlobalData, WinitState> for ContentTypeManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpContentTypeManagerV1,
+        _: <WpContentTypeManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<WpContentTypeV1, GlobalData, WinitState> for ContentTypeManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpContentTypeV1,
+        _: <WpContentTypeV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+delegate_dispatch!(WinitState: [WpContentTypeManagerV1: GlobalData] => ContentTypeManager);
+delegate_dispatch!(WinitState: [WpContentTypeV1: GlobalData] => ContentTypeManager);
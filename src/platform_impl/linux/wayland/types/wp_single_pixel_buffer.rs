@@ -0,0 +1,79 @@
+//! Handling of the single pixel buffers.
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_buffer::{self, WlBuffer};
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::single_pixel_buffer::v1::client::wp_single_pixel_buffer_manager_v1::WpSinglePixelBufferManagerV1;
+
+use sctk::globals::GlobalData;
+
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Single pixel buffer manager.
+#[derive(Debug)]
+pub struct SinglePixelBufferManager {
+    manager: WpSinglePixelBufferManagerV1,
+}
+
+/// The data associated with the single pixel buffer.
+#[derive(Debug)]
+pub struct SinglePixelBufferData;
+
+impl SinglePixelBufferManager {
+    /// Create new single pixel buffer manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    /// Create the 1x1 buffer filled with the given RGBA color.
+    ///
+    /// The buffer is destroyed once it's released by the compositor.
+    pub fn create_buffer(
+        &self,
+        color: [f32; 4],
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> WlBuffer {
+        // The protocol expects the premultiplied values.
+        let alpha = color[3].clamp(0., 1.) as f64;
+        let [r, g, b, a] = [color[0], color[1], color[2], 1.]
+            .map(|channel| (channel.clamp(0., 1.) as f64 * alpha * u32::MAX as f64) as u32);
+        self.manager
+            .create_u32_rgba_buffer(r, g, b, a, queue_handle, SinglePixelBufferData)
+    }
+}
+
+impl Dispatch<WpSinglePixelBufferManagerV1, GlobalData, WinitState> for SinglePixelBufferManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpSinglePixelBufferManagerV1,
+        _: <WpSinglePixelBufferManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<WlBuffer, SinglePixelBufferData, WinitState> for SinglePixelBufferManager {
+    fn event(
+        _: &mut WinitState,
+        buffer: &WlBuffer,
+        event: <WlBuffer as Proxy>::Event,
+        _: &SinglePixelBufferData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        if let wl_buffer::Event::Release = event {
+            buffer.destroy();
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [WpSinglePixelBufferManagerV1: GlobalData] => SinglePixelBufferManager);
+delegate_dispatch!(WinitState: [WlBuffer: SinglePixelBufferData] => SinglePixelBufferManager);
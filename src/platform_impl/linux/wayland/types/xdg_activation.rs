@@ -1,7 +1,7 @@
-//! Handling of xdg activation, which is used for user attention requests.
+//! Handling of xdg activation, which is used for user attention requests and focus handoff.
 
 use std::sync::atomic::AtomicBool;
-use std::sync::Weak;
+use std::sync::{Mutex, Weak};
 
 use sctk::reexports::client::delegate_dispatch;
 use sctk::reexports::client::globals::BindError;
@@ -78,6 +78,28 @@ impl Dispatch<XdgActivationTokenV1, XdgActivationTokenData, WinitState> for XdgA
     }
 }
 
+impl Dispatch<XdgActivationTokenV1, XdgActivationTokenCallback, WinitState> for XdgActivationState {
+    fn event(
+        _state: &mut WinitState,
+        proxy: &XdgActivationTokenV1,
+        event: <XdgActivationTokenV1 as Proxy>::Event,
+        data: &XdgActivationTokenCallback,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let token = match event {
+            ActivationTokenEvent::Done { token } => token,
+            _ => return,
+        };
+
+        if let Some(callback) = data.callback.lock().unwrap().take() {
+            callback(token);
+        }
+
+        proxy.destroy();
+    }
+}
+
 /// The data associated with the activation request.
 pub struct XdgActivationTokenData {
     /// The surface we're raising.
@@ -99,5 +121,24 @@ impl XdgActivationTokenData {
     }
 }
 
+/// The callback receiving the activation token.
+pub type ActivationTokenCallback = Box<dyn FnOnce(String) + Send>;
+
+/// The data associated with the activation token requested by the user.
+pub struct XdgActivationTokenCallback {
+    /// The callback receiving the token.
+    callback: Mutex<Option<ActivationTokenCallback>>,
+}
+
+impl XdgActivationTokenCallback {
+    /// Create a new data, which passes the token to the `callback` on completion.
+    pub fn new(callback: ActivationTokenCallback) -> Self {
+        Self {
+            callback: Mutex::new(Some(callback)),
+        }
+    }
+}
+
 delegate_dispatch!(WinitState: [ XdgActivationV1: GlobalData] => XdgActivationState);
 delegate_dispatch!(WinitState: [ XdgActivationTokenV1: XdgActivationTokenData] => XdgActivationState);
+delegate_dispatch!(WinitState: [ XdgActivationTokenV1: XdgActivationTokenCallback] => XdgActivationState);
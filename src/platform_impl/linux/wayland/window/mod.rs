@@ -13,11 +13,12 @@ use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::Proxy;
 use sctk::reexports::client::QueueHandle;
 
-use sctk::compositor::{CompositorState, Region, SurfaceData};
+use sctk::compositor::SurfaceData;
 use sctk::reexports::protocols::xdg::activation::v1::client::xdg_activation_v1::XdgActivationV1;
 use sctk::shell::xdg::window::Window as SctkWindow;
-use sctk::shell::xdg::window::WindowDecorations;
+use sctk::shell::xdg::window::{DecorationMode as SctkDecorationMode, WindowDecorations};
 use sctk::shell::WaylandSurface;
+use sctk::subcompositor::SubcompositorState;
 
 use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
@@ -32,14 +33,19 @@ use crate::window::{
 };
 
 use super::event_loop::sink::EventSink;
-use super::output::MonitorHandle;
+use super::output::{BufferTransform, MonitorHandle};
+use super::seat::{ClipboardCallback, DragCallback, TabletToolState};
 use super::state::WinitState;
-use super::types::xdg_activation::XdgActivationTokenData;
+use super::types::wl_subsurface::Subsurface;
+use super::types::wp_content_type::ContentType;
+use super::types::xdg_activation::{
+    ActivationTokenCallback, XdgActivationTokenCallback, XdgActivationTokenData,
+};
 use super::{EventLoopWindowTarget, WindowId};
 
 mod state;
 
-pub use state::WindowState;
+pub use state::{CursorGrabLifetime, TiledEdges, TitlebarDoubleClickAction, WindowState};
 
 /// The Wayland window.
 pub struct Window {
@@ -52,9 +58,6 @@ pub struct Window {
     /// The state of the window.
     window_state: Arc<Mutex<WindowState>>,
 
-    /// Compositor to handle WlRegion stuff.
-    compositor: Arc<CompositorState>,
-
     /// The wayland display used solely for raw window handle.
     display: WlDisplay,
 
@@ -67,6 +70,9 @@ pub struct Window {
     /// Handle to the main queue to perform requests.
     queue_handle: QueueHandle<WinitState>,
 
+    /// Subcompositor to create subsurfaces.
+    subcompositor: Arc<SubcompositorState>,
+
     /// Window requests to the event loop.
     window_requests: Arc<WindowRequests>,
 
@@ -92,7 +98,7 @@ impl Window {
         let monitors = state.monitors.clone();
 
         let surface = state.compositor_state.create_surface(&queue_handle);
-        let compositor = state.compositor_state.clone();
+        let subcompositor = state.subcompositor_state.clone();
         let xdg_activation = state
             .xdg_activation
             .as_ref()
@@ -120,6 +126,10 @@ impl Window {
             attributes.preferred_theme,
         );
 
+        // Follow the color scheme the system already prefers.
+        #[cfg(feature = "wayland-color-scheme")]
+        window_state.reload_system_theme(state.system_theme);
+
         // Set the app_id.
         if let Some(name) = platform_attributes.name.map(|name| name.general) {
             window.set_app_id(name);
@@ -128,30 +138,58 @@ impl Window {
         // Set the window title.
         window_state.set_title(attributes.title);
 
+        // Set the accent color of the titlebar.
+        #[cfg(feature = "sctk-adwaita")]
+        if platform_attributes.accent_color.is_some() {
+            window_state.set_accent_color(platform_attributes.accent_color);
+        }
+
+        // Set the content type hint.
+        if platform_attributes.content_type != ContentType::None {
+            window_state.set_content_type(platform_attributes.content_type);
+        }
+
+        // Set the initial transparency hint.
+        window_state.set_transparent(attributes.transparent);
+
+        // Set the initial IME purpose and cursor icon, so they apply once a seat enters.
+        window_state.set_ime_purpose(platform_attributes.ime_purpose);
+        window_state.set_cursor(platform_attributes.cursor_icon);
+
         // Set the min and max sizes.
+        if let Some(min_window_size) = platform_attributes.min_window_size {
+            window_state.set_min_window_size(min_window_size);
+        }
         let min_size = attributes.min_inner_size.map(|size| size.to_logical(1.));
         let max_size = attributes.max_inner_size.map(|size| size.to_logical(1.));
         window_state.set_min_inner_size(min_size);
         window_state.set_max_inner_size(max_size);
+        let increments = attributes
+            .resize_increments
+            .map(|increments| increments.to_logical(1.));
+        window_state.set_resize_increments(increments);
 
         // Non-resizable implies that the min and max sizes are set to the same value.
         window_state.set_resizable(attributes.resizable);
 
-        // Set startup mode.
+        // Set the buttons shown on the client side decorations.
+        window_state.set_enabled_buttons(attributes.enabled_buttons);
+
+        // Set startup mode before the initial commit, so the first configure already has it.
         match attributes.fullscreen.map(Into::into) {
             Some(Fullscreen::Exclusive(_)) => {
                 warn!("`Fullscreen::Exclusive` is ignored on Wayland");
             }
             Some(Fullscreen::Borderless(monitor)) => {
-                let output = monitor.and_then(|monitor| match monitor {
-                    PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
+                let monitor = monitor.and_then(|monitor| match monitor {
+                    PlatformMonitorHandle::Wayland(monitor) => Some(monitor),
                     #[cfg(x11_platform)]
                     PlatformMonitorHandle::X(_) => None,
                 });
 
-                window.set_fullscreen(output.as_ref())
+                window_state.set_fullscreen(monitor.as_ref())
             }
-            _ if attributes.maximized => window.set_maximized(),
+            _ if attributes.maximized => window_state.set_maximized(true),
             _ => (),
         };
 
@@ -207,9 +245,9 @@ impl Window {
             display,
             monitors,
             window_id,
-            compositor,
             window_state,
             queue_handle,
+            subcompositor,
             xdg_activation,
             attention_requested: Arc::new(AtomicBool::new(false)),
             event_loop_awakener,
@@ -278,13 +316,45 @@ impl Window {
         window_state.outer_size().to_physical(scale_factor)
     }
 
+    #[inline]
+    pub fn frame_insets(&self) -> (u32, u32, u32, u32) {
+        let window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        let (top, right, bottom, left) = window_state.frame_insets();
+        let top_left: PhysicalSize<u32> = LogicalSize::new(left, top).to_physical(scale_factor);
+        let bottom_right: PhysicalSize<u32> =
+            LogicalSize::new(right, bottom).to_physical(scale_factor);
+        (
+            top_left.height,
+            bottom_right.width,
+            bottom_right.height,
+            top_left.width,
+        )
+    }
+
     #[inline]
     pub fn set_inner_size(&self, size: Size) {
+        let _ = self.request_inner_size(size);
+    }
+
+    #[inline]
+    pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         // TODO should we issue the resize event? I don't think other platforms do so.
         let mut window_state = self.window_state.lock().unwrap();
         let scale_factor = window_state.scale_factor();
-        window_state.resize(size.to_logical::<u32>(scale_factor));
+        let new_size = window_state
+            .request_inner_size(size.to_logical::<u32>(scale_factor))?
+            .to_physical(scale_factor);
+        drop(window_state);
+
+        self.request_redraw();
 
+        Some(new_size)
+    }
+
+    #[inline]
+    pub fn set_min_window_size(&self, size: LogicalSize<u32>) {
+        self.window_state.lock().unwrap().set_min_window_size(size);
         self.request_redraw();
     }
 
@@ -312,12 +382,21 @@ impl Window {
 
     #[inline]
     pub fn resize_increments(&self) -> Option<PhysicalSize<u32>> {
-        None
+        let window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        window_state
+            .resize_increments()
+            .map(|increments| increments.to_physical(scale_factor))
     }
 
     #[inline]
-    pub fn set_resize_increments(&self, _increments: Option<Size>) {
-        warn!("`set_resize_increments` is not implemented for Wayland");
+    pub fn set_resize_increments(&self, increments: Option<Size>) {
+        let scale_factor = self.scale_factor();
+        let increments = increments.map(|increments| increments.to_logical(scale_factor));
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_resize_increments(increments);
     }
 
     #[inline]
@@ -358,14 +437,16 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_enabled_buttons(&self, _buttons: WindowButtons) {
-        // TODO(kchibisov) v5 of the xdg_shell allows that.
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_enabled_buttons(buttons);
     }
 
     #[inline]
     pub fn enabled_buttons(&self) -> WindowButtons {
-        // TODO(kchibisov) v5 of the xdg_shell allows that.
-        WindowButtons::all()
+        self.window_state.lock().unwrap().enabled_buttons()
     }
 
     #[inline]
@@ -383,6 +464,30 @@ impl Window {
         self.window_state.lock().unwrap().is_decorated()
     }
 
+    #[inline]
+    pub fn decoration_mode(&self) -> Option<DecorationMode> {
+        self.window_state
+            .lock()
+            .unwrap()
+            .decoration_mode()
+            .map(Into::into)
+    }
+
+    #[inline]
+    pub fn is_activated(&self) -> bool {
+        self.window_state.lock().unwrap().is_activated()
+    }
+
+    #[inline]
+    pub fn tiled_edges(&self) -> TiledEdges {
+        self.window_state.lock().unwrap().tiling_state()
+    }
+
+    #[inline]
+    pub fn tablet_tool_state(&self, id: u64) -> Option<TabletToolState> {
+        self.window_state.lock().unwrap().tablet_tool_state(id)
+    }
+
     #[inline]
     pub fn set_minimized(&self, minimized: bool) {
         // You can't unminimize the window on Wayland.
@@ -391,7 +496,7 @@ impl Window {
             return;
         }
 
-        self.window.set_minimized();
+        self.window_state.lock().unwrap().set_minimized();
     }
 
     #[inline]
@@ -407,11 +512,7 @@ impl Window {
 
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
-        if maximized {
-            self.window.set_maximized()
-        } else {
-            self.window.unset_maximized()
-        }
+        self.window_state.lock().unwrap().set_maximized(maximized);
     }
 
     #[inline]
@@ -440,15 +541,18 @@ impl Window {
                 warn!("`Fullscreen::Exclusive` is ignored on Wayland");
             }
             Some(Fullscreen::Borderless(monitor)) => {
-                let output = monitor.and_then(|monitor| match monitor {
-                    PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
+                let monitor = monitor.and_then(|monitor| match monitor {
+                    PlatformMonitorHandle::Wayland(monitor) => Some(monitor),
                     #[cfg(x11_platform)]
                     PlatformMonitorHandle::X(_) => None,
                 });
 
-                self.window.set_fullscreen(output.as_ref())
+                self.window_state
+                    .lock()
+                    .unwrap()
+                    .set_fullscreen(monitor.as_ref())
             }
-            None => self.window.unset_fullscreen(),
+            None => self.window_state.lock().unwrap().unset_fullscreen(),
         }
     }
 
@@ -489,11 +593,62 @@ impl Window {
         xdg_activation_token.commit();
     }
 
+    pub fn request_activation_token(
+        &self,
+        callback: ActivationTokenCallback,
+    ) -> Result<(), ExternalError> {
+        let xdg_activation = match self.xdg_activation.as_ref() {
+            Some(xdg_activation) => xdg_activation,
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        let data = XdgActivationTokenCallback::new(callback);
+        let xdg_activation_token = xdg_activation.get_activation_token(&self.queue_handle, data);
+        xdg_activation_token.set_surface(self.surface());
+        xdg_activation_token.commit();
+
+        Ok(())
+    }
+
+    pub fn activate(&self, token: String) {
+        match self.xdg_activation.as_ref() {
+            Some(xdg_activation) => xdg_activation.activate(token, self.surface()),
+            None => warn!("`activate` isn't supported"),
+        }
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         self.window_state.lock().unwrap().set_cursor_grab(mode)
     }
 
+    #[inline]
+    pub fn set_cursor_grab_lifetime(&self, lifetime: CursorGrabLifetime) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_cursor_grab_lifetime(lifetime);
+    }
+
+    #[inline]
+    pub fn set_cursor_regrab_on_focus(&self, regrab_on_focus: bool) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_cursor_regrab_on_focus(regrab_on_focus);
+    }
+
+    #[inline]
+    pub fn set_cursor_confine_region(
+        &self,
+        region: Option<Vec<(i32, i32, u32, u32)>>,
+    ) -> Result<(), ExternalError> {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_cursor_confine_region(region)
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, position: Position) -> Result<(), ExternalError> {
         let scale_factor = self.scale_factor();
@@ -506,6 +661,35 @@ impl Window {
             .map(|_| self.request_redraw())
     }
 
+    #[inline]
+    pub fn start_drag(
+        &self,
+        mime_data: Vec<(String, Vec<u8>)>,
+        callback: DragCallback,
+    ) -> Result<(), ExternalError> {
+        self.window_state
+            .lock()
+            .unwrap()
+            .start_drag(mime_data, callback)
+    }
+
+    #[inline]
+    pub fn set_clipboard(&self, mime_data: Vec<(String, Vec<u8>)>) -> Result<(), ExternalError> {
+        self.window_state.lock().unwrap().set_clipboard(mime_data)
+    }
+
+    #[inline]
+    pub fn read_clipboard(
+        &self,
+        mime_type: String,
+        callback: ClipboardCallback,
+    ) -> Result<(), ExternalError> {
+        self.window_state
+            .lock()
+            .unwrap()
+            .read_clipboard(mime_type, callback)
+    }
+
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         self.window_state.lock().unwrap().drag_window()
@@ -513,21 +697,108 @@ impl Window {
 
     #[inline]
     pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
-        let surface = self.window.wl_surface();
+        self.window_state.lock().unwrap().set_interactive(hittest);
+        // The input region is double buffered.
+        self.request_redraw();
+        Ok(())
+    }
 
-        if hittest {
-            surface.set_input_region(None);
-            Ok(())
-        } else {
-            let region = Region::new(&*self.compositor).map_err(|_| {
-                ExternalError::Os(os_error!(OsError::WaylandMisc(
-                    "failed to set input region."
-                )))
-            })?;
-            region.add(0, 0, 0, 0);
-            surface.set_input_region(Some(region.wl_region()));
-            Ok(())
-        }
+    #[inline]
+    pub fn create_subsurface(&self) -> Subsurface {
+        // The empty input region lets the input fall through to the window.
+        let input_region = self.window_state.lock().unwrap().create_region(&[]);
+        Subsurface::new(
+            &self.subcompositor,
+            self.window.wl_surface(),
+            input_region.as_ref(),
+            &self.queue_handle,
+        )
+    }
+
+    #[inline]
+    pub fn set_input_region(&self, region: Option<Vec<(i32, i32, u32, u32)>>) {
+        self.window_state.lock().unwrap().set_input_region(region);
+        // The input region is double buffered.
+        self.request_redraw();
+    }
+
+    #[inline]
+    pub fn set_background_color(&self, color: [f32; 4]) -> Result<(), ExternalError> {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_background_color(color)
+    }
+
+    #[inline]
+    pub fn preferred_buffer_transform(&self) -> BufferTransform {
+        self.window_state
+            .lock()
+            .unwrap()
+            .preferred_buffer_transform()
+    }
+
+    #[inline]
+    pub fn set_buffer_transform(&self, transform: BufferTransform) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_buffer_transform(transform);
+        self.request_redraw();
+    }
+
+    #[inline]
+    pub fn buffer_transform(&self) -> BufferTransform {
+        self.window_state.lock().unwrap().buffer_transform()
+    }
+
+    #[inline]
+    pub fn set_viewport_source(
+        &self,
+        source: Option<(PhysicalPosition<f64>, PhysicalSize<f64>)>,
+    ) -> Result<(), ExternalError> {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_viewport_source(source)?;
+        self.request_redraw();
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_accent_color(&self, _accent_color: Option<[f32; 4]>) {
+        #[cfg(feature = "sctk-adwaita")]
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_accent_color(_accent_color);
+    }
+
+    #[inline]
+    pub fn set_titlebar_double_click_action(&self, action: TitlebarDoubleClickAction) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_titlebar_double_click_action(action);
+    }
+
+    #[inline]
+    pub fn set_content_type(&self, content_type: ContentType) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_content_type(content_type);
+    }
+
+    #[inline]
+    pub fn set_parent(&self, parent: Option<&Window>) {
+        let parent = parent.map(|parent| &parent.window);
+        self.window_state.lock().unwrap().set_parent(parent);
+    }
+
+    #[inline]
+    pub fn set_idle_inhibit(&self, inhibit: bool) {
+        self.window_state.lock().unwrap().set_idle_inhibit(inhibit);
     }
 
     #[inline]
@@ -540,6 +811,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: String, cursor: usize, anchor: usize) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_ime_surrounding_text(text, cursor, anchor);
+    }
+
     #[inline]
     pub fn set_ime_allowed(&self, allowed: bool) {
         let mut window_state = self.window_state.lock().unwrap();
@@ -575,6 +854,11 @@ impl Window {
         data.outputs().next().map(MonitorHandle::new)
     }
 
+    #[inline]
+    pub fn current_monitors(&self) -> Vec<MonitorHandle> {
+        self.window_state.lock().unwrap().current_monitors()
+    }
+
     #[inline]
     pub fn available_monitors(&self) -> Vec<MonitorHandle> {
         self.monitors.lock().unwrap().clone()
@@ -643,6 +927,25 @@ impl WindowRequests {
     }
 }
 
+/// The side drawing the window decorations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecorationMode {
+    /// The decorations are drawn by the client.
+    Client,
+
+    /// The decorations are drawn by the compositor.
+    Server,
+}
+
+impl From<SctkDecorationMode> for DecorationMode {
+    fn from(mode: SctkDecorationMode) -> Self {
+        match mode {
+            SctkDecorationMode::Client => Self::Client,
+            SctkDecorationMode::Server => Self::Server,
+        }
+    }
+}
+
 impl TryFrom<&str> for Theme {
     type Error = ();
 
@@ -4,42 +4,60 @@ use std::mem::ManuallyDrop;
 use std::num::NonZeroU32;
 use std::sync::{Arc, Weak};
 
-use log::warn;
+use log::{info, warn};
+use unicode_segmentation::UnicodeSegmentation;
 
+use sctk::reexports::client::protocol::wl_data_device::WlDataDevice;
+use sctk::reexports::client::protocol::wl_data_device_manager::{DndAction, WlDataDeviceManager};
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_shm::WlShm;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::content_type::v1::client::wp_content_type_v1::WpContentTypeV1;
 use sctk::reexports::protocols::wp::fractional_scale::v1::client::wp_fractional_scale_v1::WpFractionalScaleV1;
+use sctk::reexports::protocols::wp::idle_inhibit::zv1::client::zwp_idle_inhibitor_v1::ZwpIdleInhibitorV1;
+use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_pointer_constraints_v1::Lifetime;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::reexports::protocols::wp::viewporter::client::wp_viewport::WpViewport;
 use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::ResizeEdge;
 
 use sctk::compositor::{CompositorState, Region, SurfaceData};
+use sctk::data_device_manager::data_device::{DataDeviceData, DataDeviceDataExt};
+use sctk::data_device_manager::data_offer::DataOfferError;
 use sctk::seat::pointer::ThemedPointer;
 use sctk::shell::xdg::frame::{DecorationsFrame, FrameAction, FrameClick};
-use sctk::shell::xdg::window::{DecorationMode, Window, WindowConfigure};
+use sctk::shell::xdg::window::{
+    DecorationMode, Window, WindowConfigure, WindowManagerCapabilities,
+};
 use sctk::shell::xdg::XdgSurface;
 use sctk::shell::WaylandSurface;
 use sctk::shm::Shm;
 use sctk::subcompositor::SubcompositorState;
 
-use crate::dpi::{LogicalPosition, LogicalSize};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use crate::error::{ExternalError, NotSupportedError};
 use crate::platform_impl::WindowId;
-use crate::window::{CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme};
+use crate::window::{
+    CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, WindowButtons,
+};
 
+use crate::platform_impl::wayland::output::{BufferTransform, MonitorHandle};
 use crate::platform_impl::wayland::seat::{
-    PointerConstraintsState, WinitPointerData, WinitPointerDataExt, ZwpTextInputV3Ext,
+    cursor_icon_to_shape, read_selection, ClipboardCallback, DragCallback, PointerConstraintsState,
+    SurroundingText, TabletToolState, WinitDataSourceData, WinitPointerData, WinitPointerDataExt,
+    ZwpTextInputV3Ext,
 };
 use crate::platform_impl::wayland::state::{WindowCompositorUpdate, WinitState};
+use crate::platform_impl::wayland::types::wp_content_type::ContentType;
+use crate::platform_impl::wayland::types::wp_idle_inhibit::IdleInhibitManager;
+use crate::platform_impl::wayland::types::wp_single_pixel_buffer::SinglePixelBufferManager;
 
 #[cfg(feature = "sctk-adwaita")]
 pub type WinitFrame = sctk_adwaita::AdwaitaFrame<WinitState>;
 #[cfg(not(feature = "sctk-adwaita"))]
 pub type WinitFrame = sctk::shell::xdg::frame::fallback_frame::FallbackFrame<WinitState>;
 
-// Minimum window inner size.
+// The default minimum window inner size.
 const MIN_WINDOW_SIZE: LogicalSize<u32> = LogicalSize::new(2, 1);
 
 /// The state of the window which is being updated from the [`WinitState`].
@@ -62,6 +80,9 @@ pub struct WindowState {
     /// The pointers observed on the window.
     pub pointers: Vec<Weak<ThemedPointer<WinitPointerData>>>,
 
+    /// The seat and the serial of the touch down while the window is touched.
+    touch_down: Option<(WlSeat, u32)>,
+
     /// Cursor icon.
     pub cursor_icon: CursorIcon,
 
@@ -77,12 +98,26 @@ pub struct WindowState {
     /// Theme varaint.
     theme: Option<Theme>,
 
+    /// The preferred theme of the system, used without the explicit theme.
+    #[cfg(feature = "sctk-adwaita")]
+    system_theme: Option<Theme>,
+
+    /// The accent color of the CSD titlebar.
+    #[cfg(feature = "sctk-adwaita")]
+    accent_color: Option<[f32; 4]>,
+
     /// The current window title.
     title: String,
 
     /// Whether the frame is resizable.
     resizable: bool,
 
+    /// The buttons shown on the frame.
+    enabled_buttons: WindowButtons,
+
+    /// The action performed on the double click on the frame titlebar.
+    titlebar_double_click_action: TitlebarDoubleClickAction,
+
     /// Whether the window has focus.
     has_focus: bool,
 
@@ -92,6 +127,12 @@ pub struct WindowState {
     /// Whether the window is transparent.
     transparent: bool,
 
+    /// The opaque region set on the surface, when the window is not transparent.
+    opaque_region: Option<Region>,
+
+    /// The window geometry last sent to the compositor as `(x, y, width, height)`.
+    window_geometry: Option<(i32, i32, i32, i32)>,
+
     /// The state of the compositor to create WlRegions.
     compositor: Arc<CompositorState>,
 
@@ -104,17 +145,36 @@ pub struct WindowState {
     /// The current IME purpose.
     ime_purpose: ImePurpose,
 
+    /// The text around the cursor for the IME.
+    ime_surrounding_text: Option<SurroundingText>,
+
     /// The text inputs observed on the window.
     text_inputs: Vec<ZwpTextInputV3>,
 
+    /// The text input of the seat with the latest keyboard input on the window.
+    focused_text_input: Option<ZwpTextInputV3>,
+
+    /// The tablet tools in proximity of the window by their touch ids.
+    tablet_tools: Vec<(u64, TabletToolState)>,
+
     /// The inner size of the window, as in without client side decorations.
     size: LogicalSize<u32>,
 
     /// Whether the CSD fail to create, so we don't try to create them on each iteration.
     csd_fails: bool,
 
+    /// The size the window is never made smaller than.
+    min_window_size: LogicalSize<u32>,
+
+    /// The increments the floating window is resized in.
+    resize_increments: Option<LogicalSize<u32>>,
+
     /// Min size.
     min_inner_size: LogicalSize<u32>,
+
+    /// The min size requested by the user, to apply it again when the minimum window size
+    /// changes.
+    requested_min_inner_size: Option<LogicalSize<u32>>,
     max_inner_size: Option<LogicalSize<u32>>,
 
     /// The size of the window when no states were applied to it. The primary use for it
@@ -123,17 +183,97 @@ pub struct WindowState {
     stateless_size: LogicalSize<u32>,
 
     viewport: Option<WpViewport>,
+
+    /// The transform of the buffers set on the surface by the user.
+    buffer_transform: BufferTransform,
+
+    /// The source rectangle of the buffer set on the viewport by the user.
+    viewport_source: Option<(PhysicalPosition<f64>, PhysicalSize<f64>)>,
+
     fractional_scale: Option<WpFractionalScaleV1>,
+
+    /// The content type hint of the surface.
+    content_type: Option<WpContentTypeV1>,
+
+    /// Data device manager to create data sources for drags.
+    data_device_manager: Option<WlDataDeviceManager>,
+
+    /// The data device of the seat with the keyboard focus on the window and the serial of its
+    /// latest keyboard input, to access the clipboard.
+    selection_device: Option<(WlDataDevice, u32)>,
+
+    /// Single pixel buffer manager to create solid color buffers.
+    single_pixel_buffer_manager: Option<Arc<SinglePixelBufferManager>>,
+
+    /// Idle inhibit manager to create idle inhibitors.
+    idle_inhibit_manager: Option<Arc<IdleInhibitManager>>,
+
+    /// The active idle inhibitor.
+    idle_inhibitor: Option<ZwpIdleInhibitorV1>,
+}
+
+/// The action performed on the double click on the titlebar of the client side decorations.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TitlebarDoubleClickAction {
+    /// Toggle the maximized state of the window.
+    #[default]
+    Maximize,
+
+    /// Minimize the window.
+    Minimize,
+
+    /// Do nothing.
+    None,
+}
+
+bitflags! {
+    /// The edges of the window which are tiled against other windows or the screen.
+    pub struct TiledEdges: u8 {
+        const LEFT = 1 << 0;
+        const RIGHT = 1 << 1;
+        const TOP = 1 << 2;
+        const BOTTOM = 1 << 3;
+    }
+}
+
+/// How long a cursor grab lasts once the compositor releases it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorGrabLifetime {
+    /// The compositor grabs the cursor again whenever it can, e.g. once the window is focused.
+    #[default]
+    Persistent,
+
+    /// The grab ends once the compositor releases it, as if it was set to
+    /// [`CursorGrabMode::None`].
+    OneShot,
+}
+
+impl From<CursorGrabLifetime> for Lifetime {
+    fn from(lifetime: CursorGrabLifetime) -> Self {
+        match lifetime {
+            CursorGrabLifetime::Persistent => Self::Persistent,
+            CursorGrabLifetime::OneShot => Self::Oneshot,
+        }
+    }
 }
 
 /// The state of the cursor grabs.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 struct GrabState {
     /// The grab mode requested by the user.
     user_grab_mode: CursorGrabMode,
 
     /// The current grab mode.
     current_grab_mode: CursorGrabMode,
+
+    /// The rectangles the pointer is confined to, or the whole surface when `None`.
+    confine_region: Option<Vec<(i32, i32, u32, u32)>>,
+
+    /// The lifetime of the new grabs.
+    lifetime: CursorGrabLifetime,
+
+    /// Whether the grab is set again when the window gains the focus.
+    regrab_on_focus: bool,
 }
 
 impl GrabState {
@@ -141,6 +281,9 @@ impl GrabState {
         Self {
             user_grab_mode: CursorGrabMode::None,
             current_grab_mode: CursorGrabMode::None,
+            confine_region: None,
+            lifetime: CursorGrabLifetime::Persistent,
+            regrab_on_focus: false,
         }
     }
 }
@@ -176,7 +319,7 @@ impl WindowState {
                 subcompositor.clone(),
                 self.queue_handle.clone(),
                 #[cfg(feature = "sctk-adwaita")]
-                into_sctk_adwaita_config(self.theme),
+                into_sctk_adwaita_config(self.theme.or(self.system_theme), self.accent_color),
             ) {
                 Ok(mut frame) => {
                     frame.set_title(&self.title);
@@ -220,13 +363,58 @@ impl WindowState {
             }
         };
 
+        // Only the floating window could be resized in steps.
+        let new_size = if stateless {
+            self.apply_resize_increments(new_size)
+        } else {
+            new_size
+        };
+
+        let was_stateless = self.last_configure.as_ref().map(Self::is_stateless);
+        self.stateless_size =
+            next_stateless_size(was_stateless, stateless, self.size, self.stateless_size);
+
         // XXX Set the configure before doing a resize.
         self.last_configure = Some(configure);
 
+        // Update the buttons on the frame with the new capabilities.
+        self.reload_frame_capabilities();
+
         // XXX Update the new size right away.
         self.resize(new_size);
 
-        new_size
+        // The size could be clamped by the resize.
+        self.size
+    }
+
+    /// Round the size down to the resize increments, counting from the minimum size.
+    fn apply_resize_increments(&self, size: LogicalSize<u32>) -> LogicalSize<u32> {
+        let increments = match self.resize_increments {
+            Some(increments) => increments,
+            None => return size,
+        };
+
+        // The minimum size includes the borders.
+        let (border_width, border_height) = self
+            .frame
+            .as_ref()
+            .map(|frame| frame.add_borders(0, 0))
+            .unwrap_or((0, 0));
+        let base_width = self.min_inner_size.width.saturating_sub(border_width);
+        let base_height = self.min_inner_size.height.saturating_sub(border_height);
+
+        let snap = |size: u32, base: u32, increment: u32| {
+            if increment == 0 || size <= base {
+                size
+            } else {
+                size - (size - base) % increment
+            }
+        };
+
+        LogicalSize::new(
+            snap(size.width, base_width, increments.width),
+            snap(size.height, base_height, increments.height),
+        )
     }
 
     #[inline]
@@ -234,11 +422,44 @@ impl WindowState {
         !(configure.is_maximized() || configure.is_fullscreen() || configure.is_tiled())
     }
 
+    /// Request the compositor to maximize or unmaximize the window.
+    pub fn set_maximized(&self, maximized: bool) {
+        if maximized {
+            self.window.set_maximized()
+        } else {
+            self.window.unset_maximized()
+        }
+    }
+
+    /// Request the compositor to minimize the window.
+    ///
+    /// The window can't be unminimized by the client.
+    pub fn set_minimized(&self) {
+        self.window.set_minimized()
+    }
+
+    /// Request the compositor to make the window fullscreen on the given monitor, or on the one
+    /// picked by the compositor.
+    pub fn set_fullscreen(&self, monitor: Option<&MonitorHandle>) {
+        self.window
+            .set_fullscreen(monitor.map(|monitor| &monitor.proxy))
+    }
+
+    /// Request the compositor to leave the fullscreen.
+    pub fn unset_fullscreen(&self) {
+        self.window.unset_fullscreen()
+    }
+
     /// Start interacting drag resize.
     pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
         let xdg_toplevel = self.window.xdg_toplevel();
 
-        // TODO(kchibisov) handle touch serials.
+        // The touch takes precedence, since it's held down on the window.
+        if let Some((seat, serial)) = self.touch_down.as_ref() {
+            xdg_toplevel.resize(seat, *serial, direction.into());
+            return Ok(());
+        }
+
         self.apply_on_poiner(|_, data| {
             let serial = data.latest_button_serial();
             let seat = data.seat();
@@ -251,7 +472,13 @@ impl WindowState {
     /// Start the window drag.
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         let xdg_toplevel = self.window.xdg_toplevel();
-        // TODO(kchibisov) handle touch serials.
+
+        // The touch takes precedence, since it's held down on the window.
+        if let Some((seat, serial)) = self.touch_down.as_ref() {
+            xdg_toplevel._move(seat, *serial);
+            return Ok(());
+        }
+
         self.apply_on_poiner(|_, data| {
             let serial = data.latest_button_serial();
             let seat = data.seat();
@@ -261,6 +488,12 @@ impl WindowState {
         Ok(())
     }
 
+    /// Set the seat and the serial of the touch down on the window, while it's touched.
+    #[inline]
+    pub fn set_touch_down(&mut self, touch_down: Option<(WlSeat, u32)>) {
+        self.touch_down = touch_down;
+    }
+
     /// Tells whether the window should be closed.
     pub fn frame_click(
         &mut self,
@@ -271,10 +504,22 @@ impl WindowState {
         window_id: WindowId,
         updates: &mut Vec<WindowCompositorUpdate>,
     ) -> Option<bool> {
-        match self.frame.as_mut()?.on_click(click, pressed)? {
-            FrameAction::Minimize => self.window.set_minimized(),
-            FrameAction::Maximize => self.window.set_maximized(),
-            FrameAction::UnMaximize => self.window.unset_maximized(),
+        let mut action = self.frame.as_mut()?.on_click(click, pressed)?;
+
+        // The frame maximizes on press only for the titlebar double click, the buttons act on
+        // release.
+        if pressed && matches!(action, FrameAction::Maximize | FrameAction::UnMaximize) {
+            action = match self.titlebar_double_click_action {
+                TitlebarDoubleClickAction::Maximize => action,
+                TitlebarDoubleClickAction::Minimize => FrameAction::Minimize,
+                TitlebarDoubleClickAction::None => return Some(false),
+            };
+        }
+
+        match action {
+            FrameAction::Minimize => self.set_minimized(),
+            FrameAction::Maximize => self.set_maximized(true),
+            FrameAction::UnMaximize => self.set_maximized(false),
             FrameAction::Close => WinitState::queue_close(updates, window_id),
             FrameAction::Move => self.window.move_(seat, serial),
             FrameAction::Resize(edge) => self.window.resize(seat, serial, edge),
@@ -284,6 +529,12 @@ impl WindowState {
         Some(false)
     }
 
+    /// Set the action performed on the double click on the frame titlebar.
+    #[inline]
+    pub fn set_titlebar_double_click_action(&mut self, action: TitlebarDoubleClickAction) {
+        self.titlebar_double_click_action = action;
+    }
+
     pub fn frame_point_left(&mut self) {
         if let Some(frame) = self.frame.as_mut() {
             frame.click_point_left();
@@ -305,6 +556,42 @@ impl WindowState {
         self.resizable
     }
 
+    /// Set the buttons shown on the frame.
+    pub fn set_enabled_buttons(&mut self, enabled_buttons: WindowButtons) {
+        self.enabled_buttons = enabled_buttons;
+        self.reload_frame_capabilities();
+    }
+
+    /// Get the buttons shown on the frame.
+    #[inline]
+    pub fn enabled_buttons(&self) -> WindowButtons {
+        self.enabled_buttons
+    }
+
+    /// Reload the window manager capabilities on the frame, hiding the buttons disabled by the
+    /// user and the maximize button when the window can't be resized.
+    fn reload_frame_capabilities(&mut self) {
+        let mut capabilities = self
+            .last_configure
+            .as_ref()
+            .map(|configure| configure.capabilities)
+            .unwrap_or_else(WindowManagerCapabilities::all);
+
+        if !self.enabled_buttons.contains(WindowButtons::MINIMIZE) {
+            capabilities.remove(WindowManagerCapabilities::MINIMIZE);
+        }
+
+        // The window can't be maximized when its size is fixed.
+        let fixed_size = !self.resizable || self.max_inner_size == Some(self.min_inner_size);
+        if fixed_size || !self.enabled_buttons.contains(WindowButtons::MAXIMIZE) {
+            capabilities.remove(WindowManagerCapabilities::MAXIMIZE);
+        }
+
+        if let Some(frame) = self.frame.as_mut() {
+            frame.update_wm_capabilities(capabilities);
+        }
+    }
+
     /// Set the resizable state on the window.
     #[inline]
     pub fn set_resizable(&mut self, resizable: bool) {
@@ -317,7 +604,7 @@ impl WindowState {
             // Restore min/max sizes of the window.
             self.reload_min_max_hints();
         } else {
-            self.set_min_inner_size(Some(self.size));
+            self.apply_min_inner_size(Some(self.size));
             self.set_max_inner_size(Some(self.size));
         }
 
@@ -325,6 +612,8 @@ impl WindowState {
         if let Some(frame) = self.frame.as_mut() {
             frame.set_resizable(resizable);
         }
+
+        self.reload_frame_capabilities();
     }
 
     /// Whether the window is focused.
@@ -366,6 +655,71 @@ impl WindowState {
         }
     }
 
+    /// The decoration mode of the window from the latest configure.
+    ///
+    /// Returns `None` before the window was configured.
+    #[inline]
+    pub fn decoration_mode(&self) -> Option<DecorationMode> {
+        self.last_configure
+            .as_ref()
+            .map(|configure| configure.decoration_mode)
+    }
+
+    /// Whether the compositor marked the window as activated, which usually means it's drawn
+    /// as focused.
+    ///
+    /// Returns `false` before the window was configured.
+    #[inline]
+    pub fn is_activated(&self) -> bool {
+        self.last_configure
+            .as_ref()
+            .map_or(false, WindowConfigure::is_activated)
+    }
+
+    /// Get the monitors the window is currently shown on.
+    pub fn current_monitors(&self) -> Vec<MonitorHandle> {
+        self.window
+            .wl_surface()
+            .data::<SurfaceData>()
+            .map(|data| data.outputs().map(MonitorHandle::new).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the transform of the outputs the window is shown on, or the normal transform when
+    /// they disagree.
+    ///
+    /// The `wl_surface` preferred transform requires `wl_compositor` v6, which isn't bound, so
+    /// this is the closest guess.
+    pub fn preferred_buffer_transform(&self) -> BufferTransform {
+        let mut transforms = self
+            .current_monitors()
+            .into_iter()
+            .map(|monitor| monitor.transform());
+        let transform = transforms.next().unwrap_or_default();
+        if transforms.all(|other| other == transform) {
+            transform
+        } else {
+            BufferTransform::Normal
+        }
+    }
+
+    /// Get the tiled edges of the window.
+    ///
+    /// Returns an empty set before the window was configured.
+    pub fn tiling_state(&self) -> TiledEdges {
+        let configure = match self.last_configure.as_ref() {
+            Some(configure) => configure,
+            None => return TiledEdges::empty(),
+        };
+
+        let mut edges = TiledEdges::empty();
+        edges.set(TiledEdges::LEFT, configure.is_tiled_left());
+        edges.set(TiledEdges::RIGHT, configure.is_tiled_right());
+        edges.set(TiledEdges::TOP, configure.is_tiled_top());
+        edges.set(TiledEdges::BOTTOM, configure.is_tiled_bottom());
+        edges
+    }
+
     /// Create new window state.
     pub fn new(
         connection: Connection,
@@ -385,35 +739,68 @@ impl WindowState {
             .fractional_scaling_manager
             .as_ref()
             .map(|fsm| fsm.fractional_scaling(window.wl_surface(), queue_handle));
+        let content_type = winit_state
+            .content_type_manager
+            .as_ref()
+            .map(|manager| manager.content_type(window.wl_surface(), queue_handle));
+        let idle_inhibit_manager = winit_state.idle_inhibit_manager.clone();
+        let single_pixel_buffer_manager = winit_state.single_pixel_buffer_manager.clone();
+        let data_device_manager = winit_state
+            .data_device_manager
+            .as_ref()
+            .map(|manager| manager.data_device_manager().clone());
 
         Self {
             compositor,
             connection,
+            content_type,
             theme,
+            #[cfg(feature = "sctk-adwaita")]
+            system_theme: None,
+            #[cfg(feature = "sctk-adwaita")]
+            accent_color: None,
             csd_fails: false,
+            data_device_manager,
             cursor_grab_mode: GrabState::new(),
             cursor_icon: CursorIcon::Default,
             cursor_visible: true,
+            enabled_buttons: WindowButtons::all(),
+            titlebar_double_click_action: TitlebarDoubleClickAction::default(),
             fractional_scale,
             frame: None,
             has_focus: false,
+            idle_inhibit_manager,
+            idle_inhibitor: None,
             ime_allowed: false,
             ime_purpose: ImePurpose::Normal,
+            ime_surrounding_text: None,
             last_configure: None,
             max_inner_size: None,
             min_inner_size: MIN_WINDOW_SIZE,
+            requested_min_inner_size: None,
+            min_window_size: MIN_WINDOW_SIZE,
+            resize_increments: None,
             pointer_constraints,
             pointers: Default::default(),
+            touch_down: None,
             queue_handle: queue_handle.clone(),
             scale_factor: 1.,
+            selection_device: None,
             shm: winit_state.shm.wl_shm().clone(),
+            single_pixel_buffer_manager,
             size,
             stateless_size: size,
             text_inputs: Vec::new(),
+            focused_text_input: None,
+            tablet_tools: Vec::new(),
             title: String::default(),
             transparent: false,
+            opaque_region: None,
+            window_geometry: None,
             resizable: true,
             viewport,
+            viewport_source: None,
+            buffer_transform: BufferTransform::Normal,
             window: ManuallyDrop::new(window),
         }
     }
@@ -427,6 +814,28 @@ impl WindowState {
             .unwrap_or(self.size)
     }
 
+    /// Get the thickness of the frame borders around the content as the top, right, bottom, and
+    /// left insets.
+    ///
+    /// All the insets are zero when there's no frame.
+    pub fn frame_insets(&self) -> (u32, u32, u32, u32) {
+        let frame = match self.frame.as_ref() {
+            Some(frame) => frame,
+            None => return (0, 0, 0, 0),
+        };
+
+        // The location of the frame is negative relative to the content.
+        let (x, y) = frame.location();
+        let (left, top) = (x.unsigned_abs(), y.unsigned_abs());
+        let (width, height) = frame.add_borders(0, 0);
+        (
+            top,
+            width.saturating_sub(left),
+            height.saturating_sub(top),
+            left,
+        )
+    }
+
     /// Register pointer on the top-level.
     pub fn pointer_entered(&mut self, added: Weak<ThemedPointer<WinitPointerData>>) {
         self.pointers.push(added);
@@ -438,16 +847,15 @@ impl WindowState {
 
     /// Pointer has left the top-level.
     pub fn pointer_left(&mut self, removed: Weak<ThemedPointer<WinitPointerData>>) {
-        let mut new_pointers = Vec::new();
-        for pointer in self.pointers.drain(..) {
-            if let Some(pointer) = pointer.upgrade() {
-                if pointer.pointer() != removed.upgrade().unwrap().pointer() {
-                    new_pointers.push(Arc::downgrade(&pointer));
-                }
-            }
-        }
-
-        self.pointers = new_pointers;
+        // NOTE: the removed pointer could already be dropped, for example during seat teardown,
+        // so don't assume that it's still alive.
+        let removed = removed.upgrade();
+        self.pointers.retain(|pointer| match pointer.upgrade() {
+            Some(pointer) => removed
+                .as_ref()
+                .map_or(true, |removed| pointer.pointer() != removed.pointer()),
+            None => false,
+        });
     }
 
     /// Refresh the decorations frame if it's present returning whether the client should redraw.
@@ -473,25 +881,95 @@ impl WindowState {
     }
 
     /// Reissue the transparency hint to the compositor.
-    pub fn reload_transparency_hint(&self) {
+    pub fn reload_transparency_hint(&mut self) {
         let surface = self.window.wl_surface();
 
         if self.transparent {
-            surface.set_opaque_region(None);
-        } else if let Ok(region) = Region::new(&*self.compositor) {
-            region.add(0, 0, i32::MAX, i32::MAX);
-            surface.set_opaque_region(Some(region.wl_region()));
+            if self.opaque_region.take().is_some() {
+                surface.set_opaque_region(None);
+            }
+        } else if self.opaque_region.is_none() {
+            // The region covers any size, so it's only set once.
+            if let Ok(region) = Region::new(&*self.compositor) {
+                region.add(0, 0, i32::MAX, i32::MAX);
+                surface.set_opaque_region(Some(region.wl_region()));
+                self.opaque_region = Some(region);
+            } else {
+                warn!("Failed to mark window opaque.");
+            }
+        }
+    }
+
+    /// Set the region of the surface accepting the input, as the rectangles in surface local
+    /// coordinates. `None` resets the region to the whole surface.
+    pub fn set_input_region(&mut self, region: Option<Vec<(i32, i32, u32, u32)>>) {
+        let surface = self.window.wl_surface();
+
+        let rects = match region {
+            Some(rects) => rects,
+            None => {
+                surface.set_input_region(None);
+                return;
+            }
+        };
+
+        // The region is copied by the compositor, so it's destroyed right away.
+        match self.create_region(&rects) {
+            Some(region) => surface.set_input_region(Some(region.wl_region())),
+            None => warn!("Failed to set the input region."),
+        }
+    }
+
+    /// Create a region out of the `(x, y, width, height)` rectangles.
+    pub fn create_region(&self, rects: &[(i32, i32, u32, u32)]) -> Option<Region> {
+        let region = Region::new(&*self.compositor).ok()?;
+        for &(x, y, width, height) in rects {
+            let width = i32::try_from(width).unwrap_or(i32::MAX);
+            let height = i32::try_from(height).unwrap_or(i32::MAX);
+            region.add(x, y, width, height);
+        }
+        Some(region)
+    }
+
+    /// Set whether the surface receives the pointer and touch input at all, by setting an empty
+    /// input region when it doesn't.
+    #[inline]
+    pub fn set_interactive(&mut self, interactive: bool) {
+        self.set_input_region(if interactive { None } else { Some(Vec::new()) });
+    }
+
+    /// Try to resize the window to the new inner size.
+    ///
+    /// The size is only applied while the window is floating, since otherwise the compositor
+    /// dictates it. Returns the applied size, or `None` when the request was ignored.
+    pub fn request_inner_size(&mut self, inner_size: LogicalSize<u32>) -> Option<LogicalSize<u32>> {
+        if self
+            .last_configure
+            .as_ref()
+            .map_or(true, Self::is_stateless)
+        {
+            self.resize(inner_size);
+            Some(self.size)
         } else {
-            warn!("Failed to mark window opaque.");
+            None
         }
     }
 
     /// Resize the window to the new inner size.
     pub fn resize(&mut self, inner_size: LogicalSize<u32>) {
+        // Zero sized surfaces are not allowed, so clamp to the minimum window size.
+        let inner_size = LogicalSize::new(
+            inner_size.width.max(self.min_window_size.width),
+            inner_size.height.max(self.min_window_size.height),
+        );
         self.size = inner_size;
 
-        // Update the stateless size.
-        if Some(true) == self.last_configure.as_ref().map(Self::is_stateless) {
+        // Update the stateless size, the window is floating until the first configure.
+        if self
+            .last_configure
+            .as_ref()
+            .map_or(true, Self::is_stateless)
+        {
             self.stateless_size = inner_size;
         }
 
@@ -516,13 +994,15 @@ impl WindowState {
         // Reload the hint.
         self.reload_transparency_hint();
 
-        // Set the window geometry.
-        self.window.xdg_surface().set_window_geometry(
-            x,
-            y,
-            outer_size.width as i32,
-            outer_size.height as i32,
-        );
+        // Set the window geometry, unless it didn't change.
+        let window_geometry = (x, y, outer_size.width as i32, outer_size.height as i32);
+        if self.window_geometry != Some(window_geometry) {
+            let (x, y, width, height) = window_geometry;
+            self.window
+                .xdg_surface()
+                .set_window_geometry(x, y, width, height);
+            self.window_geometry = Some(window_geometry);
+        }
 
         // Update the target viewport, this is used if and only if fractional scaling is in use.
         if let Some(viewport) = self.viewport.as_ref() {
@@ -548,6 +1028,15 @@ impl WindowState {
         }
 
         self.apply_on_poiner(|pointer, data| {
+            // Prefer the cursors drawn by the compositor.
+            if let Some(cursor_shape_device) = data.cursor_shape_device() {
+                cursor_shape_device.set_shape(
+                    data.latest_enter_serial(),
+                    cursor_icon_to_shape(cursor_icon),
+                );
+                return;
+            }
+
             let surface = data.cursor_surface();
             let scale_factor = surface.data::<SurfaceData>().unwrap().scale_factor();
 
@@ -566,12 +1055,34 @@ impl WindowState {
         })
     }
 
-    /// Set maximum inner window size.
+    /// Set the size the window is never made smaller than, even without the minimum inner size.
+    ///
+    /// Zero sized surfaces are not allowed, so the size is at least 1x1. The minimum inner size
+    /// hint is sent again with the new floor.
+    pub fn set_min_window_size(&mut self, size: LogicalSize<u32>) {
+        self.min_window_size = LogicalSize::new(size.width.max(1), size.height.max(1));
+        self.resize(self.size);
+
+        // The fixed size windows have the current size as the minimum.
+        if self.resizable {
+            self.apply_min_inner_size(self.requested_min_inner_size);
+        } else {
+            self.apply_min_inner_size(Some(self.size));
+        }
+    }
+
+    /// Set minimum inner window size.
     pub fn set_min_inner_size(&mut self, size: Option<LogicalSize<u32>>) {
+        self.requested_min_inner_size = size;
+        self.apply_min_inner_size(size);
+    }
+
+    /// Send the minimum inner size hint, raised to the minimum window size.
+    fn apply_min_inner_size(&mut self, size: Option<LogicalSize<u32>>) {
         // Ensure that the window has the right minimum size.
-        let mut size = size.unwrap_or(MIN_WINDOW_SIZE);
-        size.width = size.width.max(MIN_WINDOW_SIZE.width);
-        size.height = size.height.max(MIN_WINDOW_SIZE.height);
+        let mut size = size.unwrap_or(self.min_window_size);
+        size.width = size.width.max(self.min_window_size.width);
+        size.height = size.height.max(self.min_window_size.height);
 
         // Add the borders.
         let size = self
@@ -582,6 +1093,21 @@ impl WindowState {
 
         self.min_inner_size = size;
         self.window.set_min_size(Some(size.into()));
+        self.reload_frame_capabilities();
+    }
+
+    /// Set the increments the floating window is resized in.
+    ///
+    /// The compositor is not aware of them, so the sizes it configures are only rounded down.
+    #[inline]
+    pub fn set_resize_increments(&mut self, increments: Option<LogicalSize<u32>>) {
+        self.resize_increments = increments;
+    }
+
+    /// Get the increments the floating window is resized in.
+    #[inline]
+    pub fn resize_increments(&self) -> Option<LogicalSize<u32>> {
+        self.resize_increments
     }
 
     /// Set maximum inner window size.
@@ -595,6 +1121,7 @@ impl WindowState {
 
         self.max_inner_size = size;
         self.window.set_max_size(size.map(Into::into));
+        self.reload_frame_capabilities();
     }
 
     /// Set the CSD theme.
@@ -602,7 +1129,38 @@ impl WindowState {
         self.theme = theme;
         #[cfg(feature = "sctk-adwaita")]
         if let Some(frame) = self.frame.as_mut() {
-            frame.set_config(into_sctk_adwaita_config(theme))
+            frame.set_config(into_sctk_adwaita_config(
+                theme.or(self.system_theme),
+                self.accent_color,
+            ))
+        }
+    }
+
+    /// Set the accent color of the CSD titlebar.
+    #[cfg(feature = "sctk-adwaita")]
+    pub fn set_accent_color(&mut self, accent_color: Option<[f32; 4]>) {
+        self.accent_color = accent_color;
+        if let Some(frame) = self.frame.as_mut() {
+            frame.set_config(into_sctk_adwaita_config(
+                self.theme.or(self.system_theme),
+                accent_color,
+            ))
+        }
+    }
+
+    /// Reload the CSD theme after the preferred color scheme of the system changed.
+    ///
+    /// `None` means that the system has no preference, so the default theme is used.
+    #[cfg(feature = "wayland-color-scheme")]
+    pub fn reload_system_theme(&mut self, _system_theme: Option<Theme>) {
+        #[cfg(feature = "sctk-adwaita")]
+        let system_theme = {
+            self.system_theme = _system_theme;
+            _system_theme
+        };
+        #[cfg(feature = "sctk-adwaita")]
+        if let Some(frame) = self.frame.as_mut().filter(|_| self.theme.is_none()) {
+            frame.set_config(into_sctk_adwaita_config(system_theme, self.accent_color))
         }
     }
 
@@ -619,9 +1177,41 @@ impl WindowState {
         self.set_cursor_grab_inner(mode)
     }
 
+    /// Set the rectangles the pointer is confined to with [`CursorGrabMode::Confined`], or the
+    /// whole surface when `None`.
+    pub fn set_cursor_confine_region(
+        &mut self,
+        region: Option<Vec<(i32, i32, u32, u32)>>,
+    ) -> Result<(), ExternalError> {
+        self.cursor_grab_mode.confine_region = region;
+
+        // Confine again to apply the new region.
+        match self.cursor_grab_mode.current_grab_mode {
+            CursorGrabMode::Confined => self.set_cursor_grab_inner(CursorGrabMode::Confined),
+            _ => Ok(()),
+        }
+    }
+
+    /// Set the lifetime of the cursor grabs set afterwards.
+    #[inline]
+    pub fn set_cursor_grab_lifetime(&mut self, lifetime: CursorGrabLifetime) {
+        self.cursor_grab_mode.lifetime = lifetime;
+    }
+
+    /// Set whether the cursor grab is set again when the window gains the focus.
+    #[inline]
+    pub fn set_cursor_regrab_on_focus(&mut self, regrab_on_focus: bool) {
+        self.cursor_grab_mode.regrab_on_focus = regrab_on_focus;
+    }
+
+    /// The compositor released a one-shot cursor grab, which ends it.
+    pub fn cursor_grab_released(&mut self) {
+        let _ = self.set_cursor_grab(CursorGrabMode::None);
+    }
+
     /// Reload the hints for minimum and maximum sizes.
     pub fn reload_min_max_hints(&mut self) {
-        self.set_min_inner_size(Some(self.min_inner_size));
+        self.apply_min_inner_size(Some(self.min_inner_size));
         self.set_max_inner_size(self.max_inner_size);
     }
 
@@ -633,6 +1223,21 @@ impl WindowState {
             None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
         };
 
+        // The region is copied by the compositor, so it's destroyed right away.
+        let confine_region = match self.cursor_grab_mode.confine_region.as_deref() {
+            Some(rects) if mode == CursorGrabMode::Confined => match self.create_region(rects) {
+                Some(region) => Some(region),
+                None => {
+                    return Err(ExternalError::Os(os_error!(
+                        crate::platform_impl::OsError::WaylandMisc(
+                            "failed to create the confine region."
+                        )
+                    )))
+                }
+            },
+            _ => None,
+        };
+
         // Replace the current mode.
         let old_mode = std::mem::replace(&mut self.cursor_grab_mode.current_grab_mode, mode);
 
@@ -647,15 +1252,32 @@ impl WindowState {
         }
 
         let surface = self.window.wl_surface();
+        let lifetime = self.cursor_grab_mode.lifetime;
         match mode {
             CursorGrabMode::Locked => self.apply_on_poiner(|pointer, data| {
                 let pointer = pointer.pointer();
-                data.lock_pointer(pointer_constraints, surface, pointer, &self.queue_handle)
-            }),
-            CursorGrabMode::Confined => self.apply_on_poiner(|pointer, data| {
-                let pointer = pointer.pointer();
-                data.confine_pointer(pointer_constraints, surface, pointer, &self.queue_handle)
+                data.lock_pointer(
+                    pointer_constraints,
+                    surface,
+                    pointer,
+                    lifetime,
+                    &self.queue_handle,
+                )
             }),
+            CursorGrabMode::Confined => {
+                let region = confine_region.as_ref().map(Region::wl_region);
+                self.apply_on_poiner(|pointer, data| {
+                    let pointer = pointer.pointer();
+                    data.confine_pointer(
+                        pointer_constraints,
+                        surface,
+                        pointer,
+                        region,
+                        lifetime,
+                        &self.queue_handle,
+                    )
+                })
+            }
             CursorGrabMode::None => {
                 // Current lock/confine was already removed.
             }
@@ -664,18 +1286,177 @@ impl WindowState {
         Ok(())
     }
 
+    /// Start a drag from this window offering the given data, one entry per mime type.
+    ///
+    /// The drag is started from the latest button press on the window.
+    pub fn start_drag(
+        &self,
+        mime_data: Vec<(String, Vec<u8>)>,
+        callback: DragCallback,
+    ) -> Result<(), ExternalError> {
+        let manager = match self.data_device_manager.as_ref() {
+            Some(manager) => manager,
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        let (data_device, serial) =
+            match self
+                .pointers
+                .iter()
+                .filter_map(Weak::upgrade)
+                .find_map(|pointer| {
+                    let data = pointer.pointer().winit_data();
+                    data.data_device()
+                        .map(|data_device| (data_device, data.latest_button_serial()))
+                }) {
+                Some(drag) => drag,
+                None => {
+                    return Err(ExternalError::Os(os_error!(
+                        crate::platform_impl::OsError::WaylandMisc("no pointer to start the drag.")
+                    )))
+                }
+            };
+
+        let data = WinitDataSourceData::new(mime_data, Some(callback));
+        let mime_types: Vec<String> = data.mime_types().map(String::from).collect();
+        let source = manager.create_data_source(&self.queue_handle, data);
+        for mime_type in mime_types {
+            source.offer(mime_type);
+        }
+
+        if source.version() >= 3 {
+            source.set_actions(DndAction::Copy | DndAction::Move);
+        }
+
+        data_device.start_drag(Some(&source), self.window.wl_surface(), None, serial);
+
+        Ok(())
+    }
+
+    /// Set the data device and the serial of the latest keyboard input on the window.
+    pub fn set_selection_device(&mut self, data_device: Option<WlDataDevice>, serial: u32) {
+        self.selection_device = data_device.map(|data_device| (data_device, serial));
+    }
+
+    /// Forget the data device when its seat lost the keyboard focus.
+    pub fn unset_selection_device(&mut self, data_device: Option<&WlDataDevice>) {
+        if self
+            .selection_device
+            .as_ref()
+            .map(|(data_device, _)| data_device)
+            == data_device
+        {
+            self.selection_device = None;
+        }
+    }
+
+    /// Set the clipboard to the given data, one entry per mime type.
+    pub fn set_clipboard(&self, mime_data: Vec<(String, Vec<u8>)>) -> Result<(), ExternalError> {
+        let manager = match self.data_device_manager.as_ref() {
+            Some(manager) => manager,
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        let (data_device, serial) = match self.selection_device.as_ref() {
+            Some(selection_device) => selection_device,
+            None => {
+                return Err(ExternalError::Os(os_error!(
+                    crate::platform_impl::OsError::WaylandMisc(
+                        "clipboard can be set only with the keyboard focus."
+                    )
+                )))
+            }
+        };
+
+        let data = WinitDataSourceData::new(mime_data, None);
+        let mime_types: Vec<String> = data.mime_types().map(String::from).collect();
+        let source = manager.create_data_source(&self.queue_handle, data);
+        for mime_type in mime_types {
+            source.offer(mime_type);
+        }
+
+        // The previous source is cancelled by the compositor.
+        data_device.set_selection(Some(&source), *serial);
+        let _ = self.connection.flush();
+
+        Ok(())
+    }
+
+    /// Read the clipboard in the given mime type, passing the data to the `callback` once it's
+    /// read.
+    pub fn read_clipboard(
+        &self,
+        mime_type: String,
+        callback: ClipboardCallback,
+    ) -> Result<(), ExternalError> {
+        if self.data_device_manager.is_none() {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        let data = match self.selection_device.as_ref() {
+            Some((data_device, _)) => data_device.data::<DataDeviceData>(),
+            None => {
+                return Err(ExternalError::Os(os_error!(
+                    crate::platform_impl::OsError::WaylandMisc(
+                        "clipboard can be read only with the keyboard focus."
+                    )
+                )))
+            }
+        };
+
+        let offer = data
+            .filter(|data| data.selection_mime_types().contains(&mime_type))
+            .and_then(|data| data.selection_offer());
+        let offer = match offer {
+            Some(offer) => offer,
+            None => {
+                return Err(ExternalError::Os(os_error!(
+                    crate::platform_impl::OsError::WaylandMisc(
+                        "clipboard doesn't have the requested mime type."
+                    )
+                )))
+            }
+        };
+
+        let pipe = offer.receive(mime_type).map_err(|err| {
+            ExternalError::Os(os_error!(crate::platform_impl::OsError::WaylandMisc(
+                match err {
+                    DataOfferError::InvalidReceive => "clipboard offer is not valid yet.",
+                    DataOfferError::Io(_) => "failed to create the pipe to read the clipboard.",
+                }
+            )))
+        })?;
+
+        // Let the source see the request, since the event loop could be waiting.
+        let _ = self.connection.flush();
+
+        read_selection(pipe, callback);
+
+        Ok(())
+    }
+
     /// Set the position of the cursor.
     pub fn set_cursor_position(&self, position: LogicalPosition<f64>) -> Result<(), ExternalError> {
         if self.pointer_constraints.is_none() {
             return Err(ExternalError::NotSupported(NotSupportedError::new()));
         }
 
-        // Positon can be set only for locked cursor.
-        if self.cursor_grab_mode.current_grab_mode != CursorGrabMode::Locked {
+        // The position can only be hinted for a locked cursor. A confined cursor can't be locked
+        // to hint it at the same time, since a surface allows one constraint per pointer.
+        let reason = match self.cursor_grab_mode.current_grab_mode {
+            CursorGrabMode::Locked => None,
+            CursorGrabMode::Confined => Some(
+                "cursor position can't be set while the cursor is confined, the compositor only \
+                 accepts position hints for locked cursor.",
+            ),
+            CursorGrabMode::None => Some(
+                "cursor position can't be set while the cursor isn't grabbed, the compositor only \
+                 accepts position hints for locked cursor.",
+            ),
+        };
+        if let Some(reason) = reason {
             return Err(ExternalError::Os(os_error!(
-                crate::platform_impl::OsError::WaylandMisc(
-                    "cursor position can be set only for locked cursor."
-                )
+                crate::platform_impl::OsError::WaylandMisc(reason)
             )));
         }
 
@@ -718,7 +1499,146 @@ impl WindowState {
     /// Should be used from routine that sends focused event.
     #[inline]
     pub fn set_has_focus(&mut self, has_focus: bool) {
+        let gained_focus = has_focus && !self.has_focus;
         self.has_focus = has_focus;
+
+        // Some compositors drop the constraints on the focus loss, so set them again.
+        let mode = self.cursor_grab_mode.user_grab_mode;
+        if gained_focus && self.cursor_grab_mode.regrab_on_focus && mode != CursorGrabMode::None {
+            let _ = self.set_cursor_grab_inner(mode);
+        }
+    }
+
+    /// Fill the window with the given RGBA color, scaling a single pixel buffer to the window size.
+    pub fn set_background_color(&mut self, color: [f32; 4]) -> Result<(), ExternalError> {
+        // The single pixel buffer could only be scaled with the viewport.
+        let (manager, viewport) = match (
+            self.single_pixel_buffer_manager.as_ref(),
+            self.viewport.as_ref(),
+        ) {
+            (Some(manager), Some(viewport)) => (manager, viewport),
+            _ => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        // The source rectangle can't be outside of the single pixel, and it's kept for the
+        // buffers of the user.
+        if self.viewport_source.is_some() {
+            return Err(ExternalError::Os(os_error!(
+                crate::platform_impl::OsError::WaylandMisc(
+                    "the background color can't be set while the viewport source is set"
+                )
+            )));
+        }
+
+        let buffer = manager.create_buffer(color, &self.queue_handle);
+        viewport.set_destination(self.size.width as _, self.size.height as _);
+
+        let surface = self.window.wl_surface();
+        surface.attach(Some(&buffer), 0, 0);
+        surface.damage_buffer(0, 0, 1, 1);
+        surface.commit();
+
+        Ok(())
+    }
+
+    /// Set the transform the buffers attached to the window were rendered with.
+    ///
+    /// The surface size, and so the viewport destination, doesn't change, but the buffers have
+    /// their width and height swapped when the transform rotates by 90 or 270 degrees.
+    pub fn set_buffer_transform(&mut self, transform: BufferTransform) {
+        self.buffer_transform = transform;
+        self.window
+            .wl_surface()
+            .set_buffer_transform(transform.into());
+    }
+
+    /// Get the transform the buffers attached to the window were rendered with.
+    pub fn buffer_transform(&self) -> BufferTransform {
+        self.buffer_transform
+    }
+
+    /// Crop the buffers attached to the window to the given rectangle, which is then scaled to
+    /// the window size. `None` shows the whole buffer.
+    ///
+    /// The rectangle is in the buffer coordinates, which are physical pixels, since the buffer
+    /// scale is 1 with fractional scaling. Only the destination of the viewport follows the window
+    /// size, so the source is kept across resizes.
+    pub fn set_viewport_source(
+        &mut self,
+        source: Option<(PhysicalPosition<f64>, PhysicalSize<f64>)>,
+    ) -> Result<(), ExternalError> {
+        let viewport = match self.viewport.as_ref() {
+            Some(viewport) => viewport,
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        match source {
+            Some((position, size)) => {
+                // The protocol forbids negative origins and empty rectangles, which is checked on
+                // the values sent, since the compositor kills the connection for them.
+                let valid = match (
+                    to_wl_fixed(position.x),
+                    to_wl_fixed(position.y),
+                    to_wl_fixed(size.width),
+                    to_wl_fixed(size.height),
+                ) {
+                    (Some(x), Some(y), Some(width), Some(height)) => {
+                        x >= 0 && y >= 0 && width > 0 && height > 0
+                    }
+                    _ => false,
+                };
+                if !valid {
+                    return Err(ExternalError::Os(os_error!(
+                        crate::platform_impl::OsError::WaylandMisc("invalid viewport source")
+                    )));
+                }
+
+                viewport.set_source(position.x, position.y, size.width, size.height);
+            }
+            None => viewport.set_source(-1., -1., -1., -1.),
+        }
+
+        self.viewport_source = source;
+
+        Ok(())
+    }
+
+    /// Hint the compositor about the kind of the window content.
+    pub fn set_content_type(&mut self, content_type: ContentType) {
+        match self.content_type.as_ref() {
+            Some(wp_content_type) => wp_content_type.set_content_type(content_type.into()),
+            None => info!("Content type manager unavailable, unable to set content type"),
+        }
+    }
+
+    /// Set the parent of the window, so it's stacked above it.
+    pub fn set_parent(&self, parent: Option<&Window>) {
+        self.window.set_parent(parent);
+    }
+
+    /// Inhibit the idle behavior of the compositor while the window is visible.
+    pub fn set_idle_inhibit(&mut self, inhibit: bool) {
+        if !inhibit {
+            if let Some(idle_inhibitor) = self.idle_inhibitor.take() {
+                idle_inhibitor.destroy();
+            }
+            return;
+        }
+
+        if self.idle_inhibitor.is_some() {
+            return;
+        }
+
+        let idle_inhibit_manager = match self.idle_inhibit_manager.as_ref() {
+            Some(idle_inhibit_manager) => idle_inhibit_manager,
+            None => {
+                info!("Idle inhibit manager unavailable, unable to inhibit idle");
+                return;
+            }
+        };
+
+        self.idle_inhibitor =
+            Some(idle_inhibit_manager.inhibit(self.window.wl_surface(), &self.queue_handle));
     }
 
     /// Returns `true` if the requested state was applied.
@@ -731,6 +1651,9 @@ impl WindowState {
             if allowed {
                 text_input.enable();
                 text_input.set_content_type_by_purpose(self.ime_purpose);
+                if let Some(surrounding_text) = self.ime_surrounding_text.as_ref() {
+                    text_input.set_surrounding_text_with(surrounding_text);
+                }
             } else {
                 text_input.disable();
             }
@@ -741,17 +1664,29 @@ impl WindowState {
     }
 
     /// Set the IME position.
+    ///
+    /// The position is only sent to the text input of the focused seat, so the IME windows of the
+    /// other seats are not placed over it.
     pub fn set_ime_position(&self, position: LogicalPosition<u32>) {
-        // XXX This won't fly unless user will have a way to request IME window per seat, since
-        // the ime windows will be overlapping, but winit doesn't expose API to specify for
-        // which seat we're setting IME position.
         let (x, y) = (position.x as i32, position.y as i32);
-        for text_input in self.text_inputs.iter() {
+        for text_input in self.focused_text_inputs() {
             text_input.set_cursor_rectangle(x, y, 0, 0);
             text_input.commit();
         }
     }
 
+    /// The text inputs of the focused seat, or all of them when no seat is known to be focused.
+    fn focused_text_inputs(&self) -> &[ZwpTextInputV3] {
+        let focused = self
+            .focused_text_input
+            .as_ref()
+            .and_then(|focused| self.text_inputs.iter().position(|t| t == focused));
+        match focused {
+            Some(position) => &self.text_inputs[position..=position],
+            None => &self.text_inputs,
+        }
+    }
+
     /// Set the IME purpose.
     pub fn set_ime_purpose(&mut self, purpose: ImePurpose) {
         self.ime_purpose = purpose;
@@ -767,6 +1702,29 @@ impl WindowState {
         self.ime_purpose
     }
 
+    /// Set the text around the cursor for the IME, with the cursor and the anchor of the
+    /// selection as byte offsets into the `text`.
+    ///
+    /// The text is trimmed around the cursor when it exceeds the protocol limit.
+    pub fn set_ime_surrounding_text(&mut self, text: String, cursor: usize, anchor: usize) {
+        let surrounding_text = SurroundingText::new(text, cursor, anchor);
+
+        // The surrounding text is reset when the text input is enabled.
+        if self.ime_allowed {
+            for text_input in &self.text_inputs {
+                text_input.set_surrounding_text_with(&surrounding_text);
+                text_input.commit();
+            }
+        }
+
+        self.ime_surrounding_text = Some(surrounding_text);
+    }
+
+    /// Get the text around the cursor for the IME.
+    pub fn ime_surrounding_text(&self) -> Option<&SurroundingText> {
+        self.ime_surrounding_text.as_ref()
+    }
+
     /// Set the scale factor for the given window.
     #[inline]
     pub fn set_scale_factor(&mut self, scale_factor: f64) {
@@ -774,7 +1732,7 @@ impl WindowState {
 
         // XXX when fractional scaling is not used update the buffer scale.
         if self.fractional_scale.is_none() {
-            let _ = self.window.set_buffer_scale(self.scale_factor as _);
+            let _ = self.window.set_buffer_scale(self.scale_factor.round() as _);
         }
     }
 
@@ -782,15 +1740,7 @@ impl WindowState {
     ///
     /// This will autmatically truncate the title to something meaningfull.
     pub fn set_title(&mut self, mut title: String) {
-        // Truncate the title to at most 1024 bytes, so that it does not blow up the protocol
-        // messages
-        if title.len() > 1024 {
-            let mut new_len = 1024;
-            while !title.is_char_boundary(new_len) {
-                new_len -= 1;
-            }
-            title.truncate(new_len);
-        }
+        truncate_title(&mut title);
 
         // Update the CSD title.
         if let Some(frame) = self.frame.as_mut() {
@@ -818,10 +1768,45 @@ impl WindowState {
 
     /// The text input left the top-level.
     #[inline]
+    /// The tablet tool with the given touch id changed its state over the window.
+    pub fn tablet_tool_changed(&mut self, id: u64, tool_state: TabletToolState) {
+        match self
+            .tablet_tools
+            .iter_mut()
+            .find(|(tool_id, _)| *tool_id == id)
+        {
+            Some((_, state)) => *state = tool_state,
+            None => self.tablet_tools.push((id, tool_state)),
+        }
+    }
+
+    /// The tablet tool with the given touch id left the proximity of the window.
+    pub fn tablet_tool_left(&mut self, id: u64) {
+        self.tablet_tools.retain(|(tool_id, _)| *tool_id != id);
+    }
+
+    /// The state of the tablet tool with the given touch id in proximity of the window.
+    pub fn tablet_tool_state(&self, id: u64) -> Option<TabletToolState> {
+        self.tablet_tools
+            .iter()
+            .find(|(tool_id, _)| *tool_id == id)
+            .map(|(_, tool_state)| tool_state.clone())
+    }
+
     pub fn text_input_left(&mut self, text_input: &ZwpTextInputV3) {
         if let Some(position) = self.text_inputs.iter().position(|t| t == text_input) {
             self.text_inputs.remove(position);
         }
+
+        if self.focused_text_input.as_ref() == Some(text_input) {
+            self.focused_text_input = None;
+        }
+    }
+
+    /// Set the text input of the seat which interacted with the window last.
+    #[inline]
+    pub fn set_focused_text_input(&mut self, text_input: Option<&ZwpTextInputV3>) {
+        self.focused_text_input = text_input.cloned();
     }
 
     /// Get the cached title.
@@ -833,6 +1818,14 @@ impl WindowState {
 
 impl Drop for WindowState {
     fn drop(&mut self) {
+        if let Some(idle_inhibitor) = self.idle_inhibitor.take() {
+            idle_inhibitor.destroy();
+        }
+
+        if let Some(content_type) = self.content_type.take() {
+            content_type.destroy();
+        }
+
         let surface = self.window.wl_surface().clone();
         unsafe {
             ManuallyDrop::drop(&mut self.window);
@@ -859,10 +1852,151 @@ impl From<ResizeDirection> for ResizeEdge {
 
 // XXX rust doesn't allow from `Option`.
 #[cfg(feature = "sctk-adwaita")]
-fn into_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
-    match theme {
+fn into_sctk_adwaita_config(
+    theme: Option<Theme>,
+    accent_color: Option<[f32; 4]>,
+) -> sctk_adwaita::FrameConfig {
+    let mut config = match theme {
         Some(Theme::Light) => sctk_adwaita::FrameConfig::light(),
         Some(Theme::Dark) => sctk_adwaita::FrameConfig::dark(),
         None => sctk_adwaita::FrameConfig::auto(),
+    };
+
+    // Only the titlebar of the active window is painted with the accent color.
+    let accent_color = accent_color.and_then(|[r, g, b, a]| {
+        sctk_adwaita::theme::Color::from_rgba(
+            r.clamp(0., 1.),
+            g.clamp(0., 1.),
+            b.clamp(0., 1.),
+            a.clamp(0., 1.),
+        )
+    });
+    if let Some(accent_color) = accent_color {
+        config.theme.active.headerbar = accent_color;
+    }
+
+    config
+}
+
+/// The floating size to restore once the window gets back to the stateless state.
+///
+/// The floating size is remembered when leaving the stateless state, so it could be restored
+/// after going through any number of states, like maximized and then fullscreen. The
+/// `was_stateless` is `None` before the first configure.
+fn next_stateless_size(
+    was_stateless: Option<bool>,
+    stateless: bool,
+    size: LogicalSize<u32>,
+    stateless_size: LogicalSize<u32>,
+) -> LogicalSize<u32> {
+    if !stateless && was_stateless.unwrap_or(true) {
+        size
+    } else {
+        stateless_size
+    }
+}
+
+/// The maximum length of the title in bytes, so that it does not blow up the protocol messages.
+const MAX_TITLE_LEN: usize = 1024;
+
+/// Truncate the title to at most [`MAX_TITLE_LEN`] bytes.
+///
+/// The title is cut between grapheme clusters, so no partial emoji or combining sequence is
+/// rendered, and only falls back to the char boundary when the first cluster doesn't fit.
+fn truncate_title(title: &mut String) {
+    if title.len() <= MAX_TITLE_LEN {
+        return;
+    }
+
+    let new_len = title
+        .grapheme_indices(true)
+        .map(|(index, grapheme)| index + grapheme.len())
+        .take_while(|&end| end <= MAX_TITLE_LEN)
+        .last()
+        .unwrap_or_else(|| {
+            let mut new_len = MAX_TITLE_LEN;
+            while !title.is_char_boundary(new_len) {
+                new_len -= 1;
+            }
+            new_len
+        });
+    title.truncate(new_len);
+}
+
+/// Convert the value to `wl_fixed` the way it's sent, or `None` when it doesn't fit.
+fn to_wl_fixed(value: f64) -> Option<i32> {
+    let fixed = value * 256.;
+    (fixed.is_finite() && fixed >= i32::MIN as f64 && fixed <= i32::MAX as f64)
+        .then_some(fixed as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn floating_size_survives_maximize_then_fullscreen() {
+        let floating = LogicalSize::new(800, 600);
+        let maximized = LogicalSize::new(1920, 1040);
+        let fullscreen = LogicalSize::new(1920, 1080);
+
+        // The initial floating configure keeps the initial size.
+        let stateless_size = next_stateless_size(None, true, floating, floating);
+        assert_eq!(stateless_size, floating);
+
+        // Maximize snapshots the floating size.
+        let stateless_size = next_stateless_size(Some(true), false, floating, stateless_size);
+        assert_eq!(stateless_size, floating);
+
+        // Fullscreen from maximized must not overwrite it with the maximized size.
+        let stateless_size = next_stateless_size(Some(false), false, maximized, stateless_size);
+        assert_eq!(stateless_size, floating);
+
+        // Restoring keeps it, so the configure without a size falls back to it.
+        let stateless_size = next_stateless_size(Some(false), true, fullscreen, stateless_size);
+        assert_eq!(stateless_size, floating);
+    }
+
+    #[test]
+    fn initial_maximized_configure_snapshots_the_requested_size() {
+        // The size could be changed after the window creation, before the first configure.
+        let initial = LogicalSize::new(800, 600);
+        let requested = LogicalSize::new(640, 480);
+        let stateless_size = next_stateless_size(None, false, requested, initial);
+        assert_eq!(stateless_size, requested);
+    }
+
+    #[test]
+    fn short_title_is_kept() {
+        let mut title = "a".repeat(MAX_TITLE_LEN);
+        truncate_title(&mut title);
+        assert_eq!(title.len(), MAX_TITLE_LEN);
+    }
+
+    #[test]
+    fn flag_across_the_limit_is_dropped() {
+        // The regional indicators are 4 bytes each.
+        let prefix = "a".repeat(MAX_TITLE_LEN - 4);
+        let mut title = format!("{prefix}\u{1F1FA}\u{1F1F8}");
+        truncate_title(&mut title);
+        assert_eq!(title, prefix);
+    }
+
+    #[test]
+    fn zwj_sequence_across_the_limit_is_dropped() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let prefix = "a".repeat(MAX_TITLE_LEN - 10);
+        let mut title = format!("{prefix}{family}b");
+        truncate_title(&mut title);
+        assert_eq!(title, prefix);
+    }
+
+    #[test]
+    fn cluster_longer_than_the_limit_is_cut_between_chars() {
+        // A single cluster of a base and combining acute accents, 2 bytes each.
+        let mut title = format!("e{}", "\u{301}".repeat(MAX_TITLE_LEN));
+        truncate_title(&mut title);
+        assert_eq!(title.len(), MAX_TITLE_LEN - 1);
+        assert!(title.starts_with('e'));
     }
 }
@@ -674,6 +674,8 @@ impl Window {
     ///
     /// - **iOS / Android:** Unsupported.
     /// - **Web:** Sets the size of the canvas element.
+    /// - **Wayland:** Ignored while the window is maximized, fullscreen, or tiled.
+    ///   See `WindowExtWayland::request_inner_size` to tell whether the size was applied.
     #[inline]
     pub fn set_inner_size<S: Into<Size>>(&self, size: S) {
         self.window.set_inner_size(size.into())
@@ -745,7 +747,7 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland / Windows / Orbital:** Always returns [`None`].
+    /// - **iOS / Android / Web / Windows / Orbital:** Always returns [`None`].
     #[inline]
     pub fn resize_increments(&self) -> Option<PhysicalSize<u32>> {
         self.window.resize_increments()
@@ -759,7 +761,10 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **macOS:** Increments are converted to logical size and then macOS rounds them to whole numbers.
-    /// - **Wayland / Windows:** Not implemented.
+    /// - **Wayland:** Best effort, since the compositor is not aware of the increments. The sizes
+    ///   the compositor picks for the floating window are rounded down to them, which could leave
+    ///   a gap during interactive resizes, especially with server side decorations.
+    /// - **Windows:** Not implemented.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     #[inline]
     pub fn set_resize_increments<S: Into<Size>>(&self, increments: Option<S>) {
@@ -857,7 +862,9 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / X11 / Orbital:** Not implemented.
+    /// - **Wayland:** Only hides the minimize and maximize buttons of the client side
+    ///   decorations. The close button is always shown.
+    /// - **X11 / Orbital:** Not implemented.
     /// - **Web / iOS / Android:** Unsupported.
     pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
         self.window.set_enabled_buttons(buttons)
@@ -867,7 +874,7 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / X11 / Orbital:** Not implemented. Always returns [`WindowButtons::all`].
+    /// - **X11 / Orbital:** Not implemented. Always returns [`WindowButtons::all`].
     /// - **Web / iOS / Android:** Unsupported. Always returns [`WindowButtons::all`].
     pub fn enabled_buttons(&self) -> WindowButtons {
         self.window.enabled_buttons()
@@ -1206,7 +1213,9 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    /// - **iOS / Android / Web / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    /// - **Wayland:** Only hints the position of the cursor locked with [`CursorGrabMode::Locked`],
+    ///   and returns an [`ExternalError::Os`] otherwise.
     #[inline]
     pub fn set_cursor_position<P: Into<Position>>(&self, position: P) -> Result<(), ExternalError> {
         self.window.set_cursor_position(position.into())
@@ -1524,6 +1533,9 @@ pub enum ImePurpose {
     /// No special hints for the IME (default).
     Normal,
     /// The IME is used for password input.
+    ///
+    /// On Wayland, the text is also marked as hidden and sensitive, so the IME doesn't show
+    /// suggestions or learn from it.
     Password,
     /// The IME is used to input into a terminal.
     ///